@@ -1,6 +1,6 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{self};
+use syn::{self, parse_quote};
 
 #[proc_macro_derive(CosmicConfigEntry)]
 pub fn cosmic_config_entry_derive(input: TokenStream) -> TokenStream {
@@ -14,7 +14,7 @@ pub fn cosmic_config_entry_derive(input: TokenStream) -> TokenStream {
 
 fn impl_cosmic_config_entry_macro(ast: &syn::DeriveInput) -> TokenStream {
     let name = &ast.ident;
-    // let generics = &ast.generics;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
 
     // Get the fields of the struct
     let fields = match ast.data {
@@ -28,7 +28,7 @@ fn impl_cosmic_config_entry_macro(ast: &syn::DeriveInput) -> TokenStream {
     let write_each_config_field = fields.iter().map(|field| {
         let field_name = &field.ident;
         quote! {
-            config.set(stringify!(#field_name), &self.#field_name)?;
+            tx.set(stringify!(#field_name), &self.#field_name)?;
         }
     });
 
@@ -43,23 +43,23 @@ fn impl_cosmic_config_entry_macro(ast: &syn::DeriveInput) -> TokenStream {
         }
     });
 
-    // // Get the existing where clause or create a new one if it doesn't exist
-    // let mut where_clause = ast
-    //     .generics
-    //     .where_clause
-    //     .clone()
-    //     .unwrap_or_else(|| parse_quote!(where));
-
-    // // Add your additional constraints to the where clause
-    // // Here, we add the constraint 'T: Debug' to all generic parameters
-    // for param in ast.generics.params.iter() {
-    //     where_clause
-    //         .predicates
-    //         .push(parse_quote!(#param: ::std::default::Default + ::serde::Serialize + ::serde::de::DeserializeOwned));
-    // }
+    // Generic structs are only implemented for the type parameters which can be
+    // serialized and have a default value to fall back to
+    let mut where_clause = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+    if !ast.generics.params.is_empty() {
+        where_clause
+            .predicates
+            .push(parse_quote!(#name #ty_generics: ::std::default::Default));
+        for field in fields.iter() {
+            let field_type = &field.ty;
+            where_clause.predicates.push(parse_quote!(
+                #field_type: ::serde::Serialize + ::serde::de::DeserializeOwned
+            ));
+        }
+    }
 
     let gen = quote! {
-        impl CosmicConfigEntry for #name {
+        impl #impl_generics CosmicConfigEntry for #name #ty_generics #where_clause {
             fn write_entry(&self, config: &Config) -> Result<(), cosmic_config::Error> {
                 let tx = config.transaction();
                 #(#write_each_config_field)*
@@ -133,6 +133,32 @@ impl Config {
         }
     }
 
+    /// Get config for the given application name and config version, with user
+    /// configuration stored under a custom path instead of the XDG config directory
+    pub fn with_custom_path(name: &str, version: u64, custom_path: PathBuf) -> Result<Self, Error> {
+        // Get libcosmic system defaults path
+        let cosmic_system_path = Path::new("/usr/share/cosmic");
+        // Append [name]/v[version]
+        let system_path = cosmic_system_path.join(name).join(format!("v{}", version));
+
+        // Append [name]/v[version]
+        let user_path = custom_path.join(name).join(format!("v{}", version));
+
+        // If the app paths are children of the cosmic paths
+        if system_path.starts_with(cosmic_system_path) && user_path.starts_with(&custom_path) {
+            // Create app user path
+            fs::create_dir_all(&user_path)?;
+            // Return Config
+            Ok(Self {
+                system_path,
+                user_path,
+            })
+        } else {
+            // Return error for invalid name
+            Err(Error::InvalidName(name.to_string()))
+        }
+    }
+
     // Start a transaction (to set multiple configs at the same time)
     pub fn transaction<'a>(&'a self) -> ConfigTransaction<'a> {
         ConfigTransaction {
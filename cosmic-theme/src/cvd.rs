@@ -0,0 +1,148 @@
+use palette::{IntoColor, LinSrgb, LinSrgba, Oklab, Srgba};
+
+use crate::{model::zip_theme, Theme};
+
+/// Minimum distance in Oklab between two component colors for them to be distinguishable
+pub const MIN_DISTINGUISHABLE_DISTANCE: f32 = 0.05;
+
+/// Color vision deficiencies which can be simulated
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ColorVisionDeficiency {
+    /// absence of long wavelength (red) cones
+    Protanopia,
+    /// absence of medium wavelength (green) cones
+    Deuteranopia,
+    /// absence of short wavelength (blue) cones
+    Tritanopia,
+}
+
+impl ColorVisionDeficiency {
+    /// all simulated color vision deficiencies
+    pub const ALL: [Self; 3] = [Self::Protanopia, Self::Deuteranopia, Self::Tritanopia];
+
+    // Machado, Oliveira & Fernandes (2009), severity 1.0, applied to linear sRGB
+    fn matrix(self) -> [[f32; 3]; 3] {
+        match self {
+            Self::Protanopia => [
+                [0.152286, 1.052583, -0.204868],
+                [0.114503, 0.786281, 0.099216],
+                [-0.003882, -0.048116, 1.051998],
+            ],
+            Self::Deuteranopia => [
+                [0.367322, 0.860646, -0.227968],
+                [0.280085, 0.672501, 0.047413],
+                [-0.011820, 0.042940, 0.968881],
+            ],
+            Self::Tritanopia => [
+                [1.255528, -0.076749, -0.178779],
+                [-0.078411, 0.930809, 0.147602],
+                [0.004733, 0.691367, 0.303900],
+            ],
+        }
+    }
+
+    /// simulate how a color is perceived with the color vision deficiency
+    pub fn simulate(self, c: Srgba) -> Srgba {
+        let alpha = c.alpha;
+        let lin: LinSrgb = c.color.into_linear();
+        let [r, g, b] = self
+            .matrix()
+            .map(|row| (row[0] * lin.red + row[1] * lin.green + row[2] * lin.blue).clamp(0.0, 1.0));
+        Srgba::from_linear(LinSrgba::new(r, g, b, alpha))
+    }
+
+    /// simulate how a theme is perceived with the color vision deficiency
+    pub fn simulate_theme(self, theme: &Theme<Srgba>) -> Theme<Srgba> {
+        let mut simulated = zip_theme(theme, theme, |c, _| self.simulate(c));
+        simulated.name = format!("{} ({:?})", theme.name, self);
+        simulated
+    }
+
+    /// find the pairs of components in a theme which become indistinguishable with the color vision deficiency
+    pub fn indistinguishable_pairs(self, theme: &Theme<Srgba>) -> Vec<IndistinguishablePair> {
+        indistinguishable_pairs(&self.simulate_theme(theme))
+    }
+}
+
+/// A pair of theme components whose colors are too close to tell apart
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndistinguishablePair {
+    /// name of the first component
+    pub first: &'static str,
+    /// name of the second component
+    pub second: &'static str,
+    /// distance between the component colors in Oklab
+    pub distance: f32,
+}
+
+/// find the pairs of components in a theme which are distinguished by color and are too close to tell apart
+pub fn indistinguishable_pairs(theme: &Theme<Srgba>) -> Vec<IndistinguishablePair> {
+    let components = [
+        ("accent", theme.accent.base),
+        ("success", theme.success.base),
+        ("warning", theme.warning.base),
+        ("destructive", theme.destructive.base),
+    ];
+
+    let mut pairs = Vec::new();
+    for (i, (first, a)) in components.iter().enumerate() {
+        for (second, b) in &components[i + 1..] {
+            let distance = distance(*a, *b);
+            if distance < MIN_DISTINGUISHABLE_DISTANCE {
+                pairs.push(IndistinguishablePair {
+                    first,
+                    second,
+                    distance,
+                });
+            }
+        }
+    }
+    pairs
+}
+
+/// perceptual distance between two colors in Oklab
+pub fn distance(a: Srgba, b: Srgba) -> f32 {
+    let a: Oklab = a.color.into_color();
+    let b: Oklab = b.color.into_color();
+    ((a.l - b.l).powi(2) + (a.a - b.a).powi(2) + (a.b - b.b).powi(2)).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use palette::Srgba;
+
+    use super::{indistinguishable_pairs, ColorVisionDeficiency};
+    use crate::Theme;
+
+    #[test]
+    fn test_gray_is_unchanged() {
+        let gray = Srgba::new(0.5, 0.5, 0.5, 0.8);
+        for cvd in ColorVisionDeficiency::ALL {
+            let simulated = cvd.simulate(gray);
+            assert!((simulated.red - gray.red).abs() < 0.01, "{cvd:?}");
+            assert!((simulated.green - gray.green).abs() < 0.01, "{cvd:?}");
+            assert!((simulated.blue - gray.blue).abs() < 0.01, "{cvd:?}");
+            assert_eq!(simulated.alpha, gray.alpha);
+        }
+    }
+
+    #[test]
+    fn test_default_themes_are_distinguishable() {
+        for theme in [Theme::dark_default(), Theme::light_default()] {
+            assert!(indistinguishable_pairs(&theme).is_empty(), "{}", theme.name);
+        }
+    }
+
+    #[test]
+    fn test_red_green_indistinguishable() {
+        let mut theme = Theme::dark_default();
+        theme.success.base = Srgba::new(0.3, 0.6, 0.2, 1.0);
+        theme.destructive.base = Srgba::new(0.62, 0.55, 0.2, 1.0);
+        assert!(indistinguishable_pairs(&theme).is_empty());
+
+        let pairs = ColorVisionDeficiency::Deuteranopia.indistinguishable_pairs(&theme);
+        assert!(pairs
+            .iter()
+            .any(|p| p.first == "success" && p.second == "destructive"));
+    }
+}
@@ -14,6 +14,10 @@ mod output;
 
 /// composite colors in srgb
 pub mod composite;
+/// simulate color vision deficiencies
+pub mod cvd;
+/// check theme files for problems
+pub mod lint;
 /// get color steps
 pub mod steps;
 /// utilities
@@ -21,5 +25,19 @@ pub mod util;
 
 /// name of cosmic theme
 pub const NAME: &'static str = "com.system76.CosmicTheme";
+/// directory of saved themes, relative to the cosmic theme directory of an XDG data directory
+pub(crate) const THEME_DIR: &str = "themes";
+/// name of the cosmic dark theme
+pub const DARK_THEME_ID: &str = "com.system76.CosmicTheme.Dark";
+/// name of the cosmic light theme
+pub const LIGHT_THEME_ID: &str = "com.system76.CosmicTheme.Light";
+/// name of the cosmic theme mode
+pub const MODE_ID: &str = "com.system76.CosmicTheme.Mode";
+/// name of the cosmic palette config
+pub const PALETTE_ID: &str = "com.system76.CosmicTheme.Palette";
+/// name of the cosmic dark theme builder
+pub const DARK_THEME_BUILDER_ID: &str = "com.system76.CosmicTheme.Dark.Builder";
+/// name of the cosmic light theme builder
+pub const LIGHT_THEME_BUILDER_ID: &str = "com.system76.CosmicTheme.Light.Builder";
 
 pub use palette;
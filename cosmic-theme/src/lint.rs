@@ -0,0 +1,525 @@
+use std::{fmt, str::FromStr};
+
+use palette::Srgba;
+use ron::{Map, Value};
+
+use crate::{composite::over, util::CssColor, CosmicPalette, Theme, ThemeBuilder, DARK_PALETTE};
+
+/// minimum contrast ratio of text on its background
+pub const MIN_CONTRAST: f32 = 4.5;
+/// minimum contrast ratio of text on its background in high contrast themes
+pub const MIN_HIGH_CONTRAST: f32 = 7.0;
+
+/// Severity of a problem found in a theme file
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// the file can be loaded, but is probably not what the author intended
+    Warning,
+    /// the file cannot be loaded or produces an unusable theme
+    Error,
+}
+
+/// A problem found in a theme file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// severity of the problem
+    pub severity: Severity,
+    /// dotted path of the field with the problem, empty for the whole file
+    pub path: String,
+    /// description of the problem
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(severity: Severity, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        if self.path.is_empty() {
+            write!(f, "{severity}: {}", self.message)
+        } else {
+            write!(f, "{severity}: {}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// Kind of data stored in a theme file
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FileKind {
+    /// a fully derived [`Theme`]
+    Theme,
+    /// a [`CosmicPalette`]
+    Palette,
+    /// the inputs of a [`ThemeBuilder`]
+    Builder,
+}
+
+impl FromStr for FileKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "theme" => Ok(Self::Theme),
+            "palette" => Ok(Self::Palette),
+            "builder" => Ok(Self::Builder),
+            _ => Err(format!(
+                "unknown file kind `{s}`, expected theme, palette or builder"
+            )),
+        }
+    }
+}
+
+impl FileKind {
+    /// guess the kind of a parsed file from its fields
+    pub fn detect(value: &Value) -> Self {
+        match value {
+            // palettes are enums, whose variant names are dropped when parsed as a value
+            Value::Seq(_) => Self::Palette,
+            Value::Map(map) if map.iter().any(|(k, _)| is_key(k, "palette")) => {
+                if map.iter().any(|(k, _)| is_key(k, "background")) {
+                    Self::Theme
+                } else {
+                    Self::Builder
+                }
+            }
+            Value::Map(map) if map.iter().any(|(k, _)| is_key(k, "neutral_0")) => Self::Palette,
+            _ => Self::Theme,
+        }
+    }
+
+    /// the fields expected in a file of this kind
+    fn expected(self) -> Value {
+        let ron = match self {
+            Self::Theme => ron::to_string(&Theme::dark_default()),
+            Self::Palette => ron::to_string(&*DARK_PALETTE),
+            Self::Builder => ron::to_string(&ThemeBuilder::dark()),
+        };
+        ron.ok()
+            .and_then(|ron| ron::from_str(&ron).ok())
+            .unwrap_or(Value::Unit)
+    }
+}
+
+/// check the contents of a theme, palette or theme builder RON file
+/// the kind of file is detected from its fields if it is not provided
+pub fn lint(source: &str, kind: Option<FileKind>) -> Vec<Diagnostic> {
+    let value: Value = match ron::from_str(source) {
+        Ok(value) => value,
+        Err(err) => return vec![Diagnostic::new(Severity::Error, "", err.to_string())],
+    };
+
+    let kind = kind.unwrap_or_else(|| FileKind::detect(&value));
+    let mut diagnostics = Vec::new();
+    check_fields(&kind.expected(), &value, "", &mut diagnostics);
+    check_colors(&value, "", &mut diagnostics);
+
+    let theme = match load(source, kind) {
+        Ok(theme) => theme,
+        Err(err) => {
+            // missing fields have already been reported
+            if !diagnostics.iter().any(|d| d.severity == Severity::Error) {
+                diagnostics.push(Diagnostic::new(Severity::Error, "", err.to_string()));
+            }
+            return diagnostics;
+        }
+    };
+
+    diagnostics.extend(lint_theme(&theme));
+    diagnostics
+}
+
+/// load a file of a kind, deriving the theme from palettes and builders
+pub fn load(source: &str, kind: FileKind) -> Result<Theme<Srgba>, ron::error::SpannedError> {
+    Ok(match kind {
+        FileKind::Theme => ron::from_str(source)?,
+        // palettes are written with css colors, like the palette config
+        FileKind::Palette => {
+            let palette: CosmicPalette<CssColor> = ron::from_str(source)?;
+            ThemeBuilder::palette(palette.into()).build()
+        }
+        FileKind::Builder => ron::from_str::<ThemeBuilder>(source)?.build(),
+    })
+}
+
+/// check the contrast, spacing and corner radii of a theme
+pub fn lint_theme(theme: &Theme<Srgba>) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    check_contrast(theme, &mut diagnostics);
+    check_monotonic(
+        "spacing",
+        &[
+            ("space_none", theme.spacing.space_none),
+            ("space_xxxs", theme.spacing.space_xxxs),
+            ("space_xxs", theme.spacing.space_xxs),
+            ("space_xs", theme.spacing.space_xs),
+            ("space_s", theme.spacing.space_s),
+            ("space_m", theme.spacing.space_m),
+            ("space_l", theme.spacing.space_l),
+            ("space_xl", theme.spacing.space_xl),
+            ("space_xxl", theme.spacing.space_xxl),
+            ("space_xxxl", theme.spacing.space_xxxl),
+        ],
+        &mut diagnostics,
+    );
+
+    let radii = &theme.corner_radii;
+    for (corner, name) in ["top_left", "top_right", "bottom_right", "bottom_left"]
+        .into_iter()
+        .enumerate()
+    {
+        check_monotonic(
+            &format!("corner_radii ({name})"),
+            &[
+                ("radius_0", radii.radius_0[corner]),
+                ("radius_xs", radii.radius_xs[corner]),
+                ("radius_s", radii.radius_s[corner]),
+                ("radius_m", radii.radius_m[corner]),
+                ("radius_l", radii.radius_l[corner]),
+                ("radius_xl", radii.radius_xl[corner]),
+            ],
+            &mut diagnostics,
+        );
+    }
+
+    diagnostics
+}
+
+/// WCAG contrast ratio of two opaque colors
+pub fn contrast_ratio(a: Srgba, b: Srgba) -> f32 {
+    let luminance = |c: Srgba| {
+        let c = c.color.into_linear();
+        0.2126 * c.red + 0.7152 * c.green + 0.0722 * c.blue
+    };
+    let (a, b) = (luminance(a), luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+fn is_key(key: &Value, name: &str) -> bool {
+    matches!(key, Value::String(key) if key == name)
+}
+
+fn get<'a>(map: &'a Map, key: &Value) -> Option<&'a Value> {
+    map.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+}
+
+fn key_name(key: &Value) -> String {
+    match key {
+        Value::String(key) => key.clone(),
+        key => format!("{key:?}"),
+    }
+}
+
+fn join(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_string()
+    } else {
+        format!("{path}.{field}")
+    }
+}
+
+/// compare the fields of a file with the fields of a default value
+fn check_fields(expected: &Value, actual: &Value, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    match (expected, actual) {
+        (Value::Map(expected), Value::Map(actual)) => {
+            check_map(expected, actual, path, diagnostics)
+        }
+        (Value::Seq(expected), Value::Seq(actual)) => {
+            // single element sequences are the contents of enum variants
+            let single = actual.len() == 1;
+            for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+                let path = if single {
+                    path.to_string()
+                } else {
+                    join(path, &i.to_string())
+                };
+                check_fields(expected, actual, &path, diagnostics);
+            }
+        }
+        (Value::Option(Some(expected)), Value::Option(Some(actual))) => {
+            check_fields(expected, actual, path, diagnostics)
+        }
+        _ => {}
+    }
+}
+
+fn check_map(expected: &Map, actual: &Map, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    for (key, expected) in expected.iter() {
+        let field = join(path, &key_name(key));
+        match get(actual, key) {
+            Some(actual) => check_fields(expected, actual, &field, diagnostics),
+            // optional fields default to `None`
+            None if matches!(expected, Value::Option(_)) => diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                field,
+                "missing optional field, defaulting to None",
+            )),
+            None => diagnostics.push(Diagnostic::new(Severity::Error, field, "missing field")),
+        }
+    }
+
+    for (key, _) in actual.iter() {
+        if get(expected, key).is_none() {
+            diagnostics.push(Diagnostic::new(
+                Severity::Warning,
+                join(path, &key_name(key)),
+                "unknown field, which will be ignored",
+            ));
+        }
+    }
+}
+
+/// check that every color has components in the range 0 to 1, and that css colors can be parsed
+fn check_colors(value: &Value, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    match value {
+        Value::Map(map) if is_css_color(map) => {
+            for (key, color) in map.iter() {
+                let valid = match color {
+                    Value::String(color) => csscolorparser::parse(color).is_ok(),
+                    _ => false,
+                };
+                if !valid {
+                    diagnostics.push(Diagnostic::new(
+                        Severity::Error,
+                        join(path, &key_name(key)),
+                        "invalid css color, expected a string such as \"#94EBEB\"",
+                    ));
+                }
+            }
+        }
+        Value::Map(map) if is_color(map) => {
+            for (key, component) in map.iter() {
+                let valid = match component {
+                    Value::Number(n) => (0.0..=1.0).contains(&n.into_f64()),
+                    _ => false,
+                };
+                if !valid {
+                    diagnostics.push(Diagnostic::new(
+                        Severity::Error,
+                        join(path, &key_name(key)),
+                        "invalid color component, expected a number from 0 to 1",
+                    ));
+                }
+            }
+        }
+        Value::Map(map) => {
+            for (key, value) in map.iter() {
+                check_colors(value, &join(path, &key_name(key)), diagnostics);
+            }
+        }
+        Value::Seq(seq) => {
+            for (i, value) in seq.iter().enumerate() {
+                let path = if seq.len() == 1 {
+                    path.to_string()
+                } else {
+                    join(path, &i.to_string())
+                };
+                check_colors(value, &path, diagnostics);
+            }
+        }
+        Value::Option(Some(value)) => check_colors(value, path, diagnostics),
+        _ => {}
+    }
+}
+
+fn is_color(map: &Map) -> bool {
+    ["red", "green", "blue"]
+        .into_iter()
+        .all(|c| map.iter().any(|(k, _)| is_key(k, c)))
+        && map.iter().all(|(k, _)| {
+            ["red", "green", "blue", "alpha"]
+                .iter()
+                .any(|c| is_key(k, c))
+        })
+}
+
+fn is_css_color(map: &Map) -> bool {
+    map.len() == 1 && map.iter().all(|(k, _)| is_key(k, "c"))
+}
+
+fn check_contrast(theme: &Theme<Srgba>, diagnostics: &mut Vec<Diagnostic>) {
+    let backdrop = theme.background.base;
+    let mut pairs = Vec::new();
+    for (name, container) in [
+        ("background", &theme.background),
+        ("primary", &theme.primary),
+        ("secondary", &theme.secondary),
+    ] {
+        let base = over(container.base, backdrop);
+        pairs.push((name.to_string(), container.on, base));
+        pairs.push((
+            format!("{name}.component"),
+            container.component.on,
+            over(container.component.base, base),
+        ));
+    }
+    for (name, component) in [
+        ("accent_button", &theme.accent_button),
+        ("success_button", &theme.success_button),
+        ("destructive_button", &theme.destructive_button),
+        ("warning_button", &theme.warning_button),
+        ("button", &theme.button),
+    ] {
+        pairs.push((
+            name.to_string(),
+            component.on,
+            over(component.base, backdrop),
+        ));
+    }
+
+    for (path, on, base) in pairs {
+        let ratio = contrast_ratio(over(on, base), base);
+        // falling short of the high contrast target is not an error, as the text is still legible
+        let (severity, min) = if ratio < MIN_CONTRAST {
+            (Severity::Error, MIN_CONTRAST)
+        } else if theme.is_high_contrast && ratio < MIN_HIGH_CONTRAST {
+            (Severity::Warning, MIN_HIGH_CONTRAST)
+        } else {
+            continue;
+        };
+        diagnostics.push(Diagnostic::new(
+            severity,
+            join(&path, "on"),
+            format!("contrast ratio with base is {ratio:.2}, expected at least {min}"),
+        ));
+    }
+}
+
+fn check_monotonic<T: PartialOrd + fmt::Display>(
+    path: &str,
+    values: &[(&str, T)],
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for pair in values.windows(2) {
+        let ((smaller, a), (larger, b)) = (&pair[0], &pair[1]);
+        if b < a {
+            diagnostics.push(Diagnostic::new(
+                Severity::Error,
+                join(path, larger),
+                format!("{larger} ({b}) is smaller than {smaller} ({a})"),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lint, FileKind, Severity};
+    use crate::{Theme, ThemeBuilder};
+
+    fn errors(source: &str) -> Vec<String> {
+        lint(source, None)
+            .into_iter()
+            .filter(|d| d.severity == Severity::Error)
+            .map(|d| d.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_defaults_are_clean() {
+        for theme in [
+            Theme::dark_default(),
+            Theme::light_default(),
+            Theme::high_contrast_dark_default(),
+            Theme::high_contrast_light_default(),
+        ] {
+            let source = ron::to_string(&theme).unwrap();
+            assert_eq!(errors(&source), Vec::<String>::new(), "{}", theme.name);
+        }
+
+        let source = ron::to_string(&ThemeBuilder::light()).unwrap();
+        assert_eq!(lint(&source, Some(FileKind::Builder)), vec![]);
+        let source = ron::to_string(&Theme::dark_default()).unwrap();
+        assert_eq!(lint(&source, None), vec![]);
+    }
+
+    #[test]
+    fn test_fields() {
+        let source =
+            ron::to_string(&Theme::dark_default())
+                .unwrap()
+                .replacen("is_dark:", "is_darkk:", 1);
+        let diagnostics = lint(&source, None);
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error && d.path == "is_dark"));
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Warning && d.path == "is_darkk"));
+    }
+
+    #[test]
+    fn test_invalid_color() {
+        let mut theme = Theme::dark_default();
+        theme.accent.base.red = 2.0;
+        let source = ron::to_string(&theme).unwrap();
+        assert_eq!(
+            errors(&source),
+            vec!["error: accent.base.red: invalid color component, expected a number from 0 to 1"]
+        );
+    }
+
+    #[test]
+    fn test_contrast_and_spacing() {
+        let mut theme = Theme::dark_default();
+        theme.primary.on = theme.primary.base;
+        theme.spacing.space_m = 1;
+        theme.corner_radii.radius_l[2] = 0.0;
+        let errors = errors(&ron::to_string(&theme).unwrap());
+
+        assert_eq!(errors.len(), 3, "{errors:#?}");
+        assert!(errors[0].starts_with("error: primary.on: contrast ratio"));
+        assert_eq!(
+            errors[1],
+            "error: spacing.space_m: space_m (1) is smaller than space_s (16)"
+        );
+        assert_eq!(
+            errors[2],
+            "error: corner_radii (bottom_right).radius_l: radius_l (0) is smaller than radius_m (16)"
+        );
+    }
+
+    #[test]
+    fn test_shipped_palettes_are_clean() {
+        for source in [
+            include_str!("model/dark.ron"),
+            include_str!("model/light.ron"),
+        ] {
+            assert_eq!(lint(source, None), vec![]);
+            assert!(super::load(source, FileKind::Palette).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_invalid_css_color() {
+        let source = include_str!("model/dark.ron").replacen("\"#94EBEB\"", "\"#94EBEZ\"", 1);
+        assert_eq!(
+            errors(&source),
+            vec!["error: blue.c: invalid css color, expected a string such as \"#94EBEB\""]
+        );
+    }
+
+    #[test]
+    fn test_detect() {
+        let palette = "Dark((name: \"test\"))";
+        assert_eq!(
+            FileKind::detect(&ron::from_str(palette).unwrap()),
+            FileKind::Palette
+        );
+        let builder = ron::to_string(&ThemeBuilder::dark()).unwrap();
+        assert_eq!(
+            FileKind::detect(&ron::from_str(&builder).unwrap()),
+            FileKind::Builder
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Command line tool for checking and regenerating COSMIC themes.
+
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use cosmic_theme::lint::{self, FileKind, Severity};
+
+const USAGE: &str = "\
+Usage: cosmic-theme <command> [--kind theme|palette|builder] <file>...
+
+Commands:
+  lint        report problems in theme, palette or theme builder RON files
+  regenerate  derive a theme from a palette or theme builder and print it as RON,
+              or print a RON list of the themes derived from several files
+
+Options:
+  --kind      kind of the files, detected from their fields by default
+  -h, --help  print this message";
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let Some(command) = args.next() else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let mut kind = None;
+    let mut files = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            "--kind" => match args.next().as_deref().map(str::parse::<FileKind>) {
+                Some(Ok(k)) => kind = Some(k),
+                Some(Err(err)) => {
+                    eprintln!("{err}");
+                    return ExitCode::FAILURE;
+                }
+                None => {
+                    eprintln!("--kind requires a value");
+                    return ExitCode::FAILURE;
+                }
+            },
+            _ => files.push(PathBuf::from(arg)),
+        }
+    }
+
+    if files.is_empty() {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    }
+
+    let result = match command.as_str() {
+        "lint" => lint_files(&files, kind),
+        "regenerate" => regenerate(&files, kind),
+        "-h" | "--help" => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        _ => {
+            eprintln!("unknown command `{command}`\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// lint every file, returning whether all of them are free of errors
+fn lint_files(files: &[PathBuf], kind: Option<FileKind>) -> Result<bool, String> {
+    let mut ok = true;
+    for file in files {
+        let source = read(file)?;
+        let diagnostics = lint::lint(&source, kind);
+        for diagnostic in &diagnostics {
+            println!("{}: {diagnostic}", file.display());
+        }
+        ok &= diagnostics.iter().all(|d| d.severity != Severity::Error);
+    }
+    Ok(ok)
+}
+
+/// print the themes derived from every file, as a list if there are several
+fn regenerate(files: &[PathBuf], kind: Option<FileKind>) -> Result<bool, String> {
+    let mut themes = Vec::with_capacity(files.len());
+    for file in files {
+        let source = read(file)?;
+        let kind = match kind {
+            Some(kind) => kind,
+            None => FileKind::detect(
+                &ron::from_str(&source).map_err(|err| format!("{}: {err}", file.display()))?,
+            ),
+        };
+        // themes are already derived, and do not keep the inputs they were derived from
+        if kind == FileKind::Theme {
+            return Err(format!(
+                "{}: themes can not be regenerated, only palettes and theme builders",
+                file.display()
+            ));
+        }
+        let theme =
+            lint::load(&source, kind).map_err(|err| format!("{}: {err}", file.display()))?;
+        themes.push(theme);
+    }
+
+    let ron = match themes.as_slice() {
+        [theme] => ron::ser::to_string_pretty(theme, Default::default()),
+        themes => ron::ser::to_string_pretty(themes, Default::default()),
+    }
+    .map_err(|err| err.to_string())?;
+    println!("{ron}");
+    Ok(true)
+}
+
+fn read(file: &PathBuf) -> Result<String, String> {
+    fs::read_to_string(file).map_err(|err| format!("{}: {err}", file.display()))
+}
@@ -0,0 +1,97 @@
+use palette::Srgba;
+use serde::{Deserialize, Serialize};
+
+/// Level of elevation of a surface above its parent
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum ElevationLevel {
+    /// surfaces drawn flat on their parent
+    #[default]
+    Flat,
+    /// surfaces slightly raised above their parent, such as cards
+    Raised,
+    /// surfaces drawn over other content, such as popovers and popups
+    Overlay,
+    /// surfaces which block other content, such as dialogs
+    Modal,
+}
+
+/// Shadow of an elevated surface
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Shadow<C> {
+    /// the color of the shadow
+    pub color: C,
+    /// the offset of the shadow from the surface
+    pub offset: [f32; 2],
+    /// the blur radius of the shadow
+    pub blur_radius: f32,
+    /// the outline drawn around the surface instead of a shadow, used by high contrast themes
+    pub border: Option<C>,
+    /// the width of the outline
+    pub border_width: f32,
+}
+
+/// Elevation variables for the Cosmic theme
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Elevation<C> {
+    /// shadow of flat surfaces
+    pub flat: Shadow<C>,
+    /// shadow of raised surfaces
+    pub raised: Shadow<C>,
+    /// shadow of overlay surfaces
+    pub overlay: Shadow<C>,
+    /// shadow of modal surfaces
+    pub modal: Shadow<C>,
+}
+
+impl<C> Elevation<C> {
+    /// get the shadow for an elevation level
+    pub fn shadow(&self, level: ElevationLevel) -> &Shadow<C> {
+        match level {
+            ElevationLevel::Flat => &self.flat,
+            ElevationLevel::Raised => &self.raised,
+            ElevationLevel::Overlay => &self.overlay,
+            ElevationLevel::Modal => &self.modal,
+        }
+    }
+}
+
+impl Elevation<Srgba> {
+    /// helper for producing elevation levels from a shadow color and a high contrast outline color
+    pub fn new(shadow: Srgba, outline: Srgba, is_dark: bool, is_high_contrast: bool) -> Self {
+        let level = |alpha: f32, offset: f32, blur_radius: f32| {
+            if is_high_contrast {
+                let mut color = shadow;
+                color.alpha = 0.0;
+                Shadow {
+                    color,
+                    offset: [0.0, 0.0],
+                    blur_radius: 0.0,
+                    border: Some(outline),
+                    border_width: 1.0,
+                }
+            } else {
+                let mut color = shadow;
+                // shadows need to be stronger to be visible on dark surfaces
+                color.alpha = if is_dark { alpha * 2.0 } else { alpha };
+                Shadow {
+                    color,
+                    offset: [0.0, offset],
+                    blur_radius,
+                    border: None,
+                    border_width: 0.0,
+                }
+            }
+        };
+
+        let mut flat = level(0.0, 0.0, 0.0);
+        flat.border = None;
+        flat.border_width = 0.0;
+
+        Self {
+            flat,
+            raised: level(0.1, 1.0, 4.0),
+            overlay: level(0.16, 4.0, 12.0),
+            modal: level(0.24, 8.0, 24.0),
+        }
+    }
+}
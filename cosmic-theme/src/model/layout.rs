@@ -1,5 +1,96 @@
-#[derive(Default)]
+use serde::{Deserialize, Serialize};
+
+use crate::{CornerRadii, Spacing};
+
+/// Density of the Cosmic layout, which scales spacing, corner radii and widget heights
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Density {
+    /// tighter spacing and smaller widgets
+    Compact,
+    /// the default layout
+    #[default]
+    Comfortable,
+    /// looser spacing and larger widgets
+    Spacious,
+}
+
+impl Density {
+    /// scale factor applied to the default layout variables
+    pub fn scale(self) -> f32 {
+        match self {
+            Density::Compact => 0.75,
+            Density::Comfortable => 1.0,
+            Density::Spacious => 1.25,
+        }
+    }
+
+    /// scale a layout variable for the density
+    pub fn scale_u16(self, value: u16) -> u16 {
+        (f32::from(value) * self.scale()).round() as u16
+    }
+
+    /// scale spacing variables for the density
+    pub fn spacing(self, spacing: Spacing) -> Spacing {
+        Spacing {
+            space_none: self.scale_u16(spacing.space_none),
+            space_xxxs: self.scale_u16(spacing.space_xxxs),
+            space_xxs: self.scale_u16(spacing.space_xxs),
+            space_xs: self.scale_u16(spacing.space_xs),
+            space_s: self.scale_u16(spacing.space_s),
+            space_m: self.scale_u16(spacing.space_m),
+            space_l: self.scale_u16(spacing.space_l),
+            space_xl: self.scale_u16(spacing.space_xl),
+            space_xxl: self.scale_u16(spacing.space_xxl),
+            space_xxxl: self.scale_u16(spacing.space_xxxl),
+        }
+    }
+
+    /// scale corner radii variables for the density
+    pub fn corner_radii(self, corner_radii: CornerRadii) -> CornerRadii {
+        let scale = |radii: [f32; 4]| radii.map(|r| (r * self.scale()).round());
+        CornerRadii {
+            radius_0: scale(corner_radii.radius_0),
+            radius_xs: scale(corner_radii.radius_xs),
+            radius_s: scale(corner_radii.radius_s),
+            radius_m: scale(corner_radii.radius_m),
+            radius_l: scale(corner_radii.radius_l),
+            radius_xl: scale(corner_radii.radius_xl),
+        }
+    }
+}
+
+/// Widget layout variables for the Cosmic theme
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Layout {
-    corner_radii: [u32;4],
-    
-}
\ No newline at end of file
+    /// density which the layout was derived from
+    pub density: Density,
+    /// minimum height of buttons
+    pub button_height: u16,
+    /// height of items in segmented buttons
+    pub segmented_button_height: u16,
+    /// height of items in view switchers
+    pub view_switcher_height: u16,
+    /// height of the header bar
+    pub header_bar_height: u16,
+    /// horizontal padding of list columns
+    pub list_padding: u16,
+}
+
+impl Default for Layout {
+    fn default() -> Self {
+        Density::default().into()
+    }
+}
+
+impl From<Density> for Layout {
+    fn from(density: Density) -> Self {
+        Self {
+            density,
+            button_height: density.scale_u16(32),
+            segmented_button_height: density.scale_u16(32),
+            view_switcher_height: density.scale_u16(48),
+            header_bar_height: density.scale_u16(50),
+            list_padding: density.scale_u16(6),
+        }
+    }
+}
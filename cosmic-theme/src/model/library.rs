@@ -0,0 +1,423 @@
+use std::{
+    env, fmt, fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use atomicwrites::{AtomicFile, OverwriteBehavior};
+use cosmic_config::CosmicConfigEntry;
+use palette::Srgba;
+
+use crate::{Theme, ThemeMode, NAME, THEME_DIR};
+
+/// file extension of saved themes
+const THEME_EXTENSION: &str = "ron";
+
+/// Errors produced by the theme library
+#[derive(Debug)]
+pub enum LibraryError {
+    /// a theme with the name already exists
+    AlreadyExists(String),
+    /// failed to read or write the active theme config
+    Config(cosmic_config::Error),
+    /// the name can not be used as a theme file name
+    InvalidName(String),
+    /// failed to read or write a theme file
+    Io(std::io::Error),
+    /// the user data directory could not be found
+    NoDataDirectory,
+    /// no theme with the name exists
+    NotFound(String),
+    /// system themes can not be modified
+    ReadOnly(String),
+    /// failed to serialize a theme
+    Ron(ron::Error),
+    /// failed to parse a theme file
+    RonSpanned(ron::error::SpannedError),
+}
+
+impl fmt::Display for LibraryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AlreadyExists(name) => write!(f, "theme '{}' already exists", name),
+            Self::Config(err) => err.fmt(f),
+            Self::InvalidName(name) => write!(f, "invalid theme name '{}'", name),
+            Self::Io(err) => err.fmt(f),
+            Self::NoDataDirectory => write!(f, "user data directory not found"),
+            Self::NotFound(name) => write!(f, "theme '{}' not found", name),
+            Self::ReadOnly(name) => write!(f, "system theme '{}' can not be modified", name),
+            Self::Ron(err) => err.fmt(f),
+            Self::RonSpanned(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LibraryError {}
+
+impl From<cosmic_config::Error> for LibraryError {
+    fn from(f: cosmic_config::Error) -> Self {
+        Self::Config(f)
+    }
+}
+
+impl From<std::io::Error> for LibraryError {
+    fn from(f: std::io::Error) -> Self {
+        Self::Io(f)
+    }
+}
+
+impl From<ron::Error> for LibraryError {
+    fn from(f: ron::Error) -> Self {
+        Self::Ron(f)
+    }
+}
+
+impl From<ron::error::SpannedError> for LibraryError {
+    fn from(f: ron::error::SpannedError) -> Self {
+        Self::RonSpanned(f)
+    }
+}
+
+/// Where a saved theme is installed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ThemeSource {
+    /// installed in the user data directory, and may be modified
+    User,
+    /// installed in a system data directory, and is read only
+    System,
+}
+
+/// Metadata of a saved theme
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeInfo {
+    /// name of the theme
+    pub name: String,
+    /// path of the theme file
+    pub path: PathBuf,
+    /// where the theme is installed
+    pub source: ThemeSource,
+    /// whether the theme is dark
+    pub is_dark: bool,
+    /// whether the theme is high contrast
+    pub is_high_contrast: bool,
+    /// accent color of the theme
+    pub accent: Srgba,
+}
+
+impl ThemeInfo {
+    fn new(theme: &Theme<Srgba>, path: PathBuf, source: ThemeSource) -> Self {
+        Self {
+            name: theme.name.clone(),
+            path,
+            source,
+            is_dark: theme.is_dark,
+            is_high_contrast: theme.is_high_contrast,
+            accent: theme.accent.base,
+        }
+    }
+}
+
+/// What to do when saving a theme with the name of an existing user theme
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Collision {
+    /// fail with [`LibraryError::AlreadyExists`]
+    #[default]
+    Fail,
+    /// replace the existing theme
+    Overwrite,
+    /// save the theme with a numbered suffix, such as "Name (2)"
+    Rename,
+}
+
+/// Library of named themes saved in the XDG data directories
+#[derive(Debug, Clone)]
+pub struct ThemeLibrary {
+    user_dir: PathBuf,
+    system_dirs: Vec<PathBuf>,
+}
+
+impl ThemeLibrary {
+    /// get the library of themes in the XDG data directories
+    pub fn new() -> Result<Self, LibraryError> {
+        let user_dir = dirs::data_dir()
+            .ok_or(LibraryError::NoDataDirectory)?
+            .join(NAME)
+            .join(THEME_DIR);
+
+        let data_dirs = env::var("XDG_DATA_DIRS")
+            .ok()
+            .filter(|dirs| !dirs.is_empty())
+            .unwrap_or_else(|| String::from("/usr/local/share:/usr/share"));
+        let system_dirs = env::split_paths(&data_dirs)
+            .map(|dir| dir.join(NAME).join(THEME_DIR))
+            .collect();
+
+        Ok(Self::with_dirs(user_dir, system_dirs))
+    }
+
+    /// get a library of themes in custom directories
+    /// system directories are searched in order of precedence
+    pub fn with_dirs(user_dir: PathBuf, system_dirs: Vec<PathBuf>) -> Self {
+        Self {
+            user_dir,
+            system_dirs,
+        }
+    }
+
+    /// list the saved themes, sorted by name
+    /// user themes shadow system themes with the same name
+    pub fn themes(&self) -> Vec<ThemeInfo> {
+        let mut themes: Vec<ThemeInfo> = Vec::new();
+        let dirs = std::iter::once((&self.user_dir, ThemeSource::User)).chain(
+            self.system_dirs
+                .iter()
+                .map(|dir| (dir, ThemeSource::System)),
+        );
+
+        for (dir, source) in dirs {
+            let Ok(entries) = fs::read_dir(dir) else {
+                continue;
+            };
+
+            for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+                if path.extension().and_then(|ext| ext.to_str()) != Some(THEME_EXTENSION) {
+                    continue;
+                }
+
+                // unreadable themes are skipped rather than failing the whole listing
+                let Ok(theme) = read_theme(&path) else {
+                    continue;
+                };
+
+                if themes.iter().all(|info| info.name != theme.name) {
+                    themes.push(ThemeInfo::new(&theme, path, source));
+                }
+            }
+        }
+
+        themes.sort_by(|a, b| a.name.cmp(&b.name));
+        themes
+    }
+
+    /// get the metadata of a saved theme
+    pub fn info(&self, name: &str) -> Result<ThemeInfo, LibraryError> {
+        self.themes()
+            .into_iter()
+            .find(|info| info.name == name)
+            .ok_or_else(|| LibraryError::NotFound(name.to_string()))
+    }
+
+    /// load a saved theme by name
+    pub fn load(&self, name: &str) -> Result<Theme<Srgba>, LibraryError> {
+        read_theme(&self.info(name)?.path)
+    }
+
+    /// save a theme to the user directory under its name
+    pub fn save(
+        &self,
+        theme: &Theme<Srgba>,
+        collision: Collision,
+    ) -> Result<ThemeInfo, LibraryError> {
+        validate_name(&theme.name)?;
+
+        let mut theme = theme.clone();
+        if self.user_path(&theme.name).exists() {
+            match collision {
+                Collision::Fail => return Err(LibraryError::AlreadyExists(theme.name)),
+                Collision::Overwrite => {}
+                Collision::Rename => {
+                    let base = theme.name.clone();
+                    theme.name = (2..)
+                        .map(|i| format!("{} ({})", base, i))
+                        .find(|name| !self.user_path(name).exists())
+                        .unwrap_or(base);
+                }
+            }
+        }
+
+        fs::create_dir_all(&self.user_dir)?;
+        let path = self.user_path(&theme.name);
+        let ron = ron::ser::to_string_pretty(&theme, Default::default())?;
+        atomic_write(&path, &ron)?;
+        Ok(ThemeInfo::new(&theme, path, ThemeSource::User))
+    }
+
+    /// rename a user theme
+    pub fn rename(&self, name: &str, new_name: &str) -> Result<ThemeInfo, LibraryError> {
+        validate_name(new_name)?;
+
+        let info = self.user_info(name)?;
+        if name != new_name && self.user_path(new_name).exists() {
+            return Err(LibraryError::AlreadyExists(new_name.to_string()));
+        }
+
+        let mut theme = read_theme(&info.path)?;
+        theme.name = new_name.to_string();
+        let saved = self.save(&theme, Collision::Overwrite)?;
+        if saved.path != info.path {
+            fs::remove_file(&info.path)?;
+        }
+        Ok(saved)
+    }
+
+    /// delete a user theme
+    pub fn delete(&self, name: &str) -> Result<(), LibraryError> {
+        let info = self.user_info(name)?;
+        fs::remove_file(info.path)?;
+        Ok(())
+    }
+
+    /// set a saved theme as the theme used in light or dark mode
+    /// the theme is also activated if its mode is the current mode
+    pub fn set_active(&self, name: &str, is_dark: bool) -> Result<Theme<Srgba>, LibraryError> {
+        let theme = self.load(name)?;
+        let config = if is_dark {
+            Theme::<Srgba>::dark_config()?
+        } else {
+            Theme::<Srgba>::light_config()?
+        };
+        theme.write_entry(&config)?;
+
+        let mode = ThemeMode::get_entry(&ThemeMode::config()?).unwrap_or_else(|(_, mode)| mode);
+        if mode.is_dark == is_dark {
+            mode.apply()?;
+        }
+
+        Ok(theme)
+    }
+
+    fn user_path(&self, name: &str) -> PathBuf {
+        self.user_dir.join(format!("{}.{}", name, THEME_EXTENSION))
+    }
+
+    fn user_info(&self, name: &str) -> Result<ThemeInfo, LibraryError> {
+        let info = self.info(name)?;
+        if info.source == ThemeSource::System {
+            return Err(LibraryError::ReadOnly(name.to_string()));
+        }
+        Ok(info)
+    }
+}
+
+fn read_theme(path: &Path) -> Result<Theme<Srgba>, LibraryError> {
+    Ok(ron::from_str(&fs::read_to_string(path)?)?)
+}
+
+fn atomic_write(path: &Path, data: &str) -> Result<(), LibraryError> {
+    AtomicFile::new(path, OverwriteBehavior::AllowOverwrite)
+        .write(|file| file.write_all(data.as_bytes()))
+        .map_err(|err| match err {
+            atomicwrites::Error::Internal(err) | atomicwrites::Error::User(err) => err.into(),
+        })
+}
+
+fn validate_name(name: &str) -> Result<(), LibraryError> {
+    if name.trim().is_empty()
+        || name.starts_with('.')
+        || name.contains(|c: char| c == '/' || c == '\\' || c.is_control())
+    {
+        return Err(LibraryError::InvalidName(name.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{Collision, LibraryError, ThemeLibrary, ThemeSource};
+    use crate::Theme;
+
+    fn library(test_name: &str) -> (ThemeLibrary, PathBuf) {
+        let root = std::env::temp_dir().join(format!(
+            "cosmic-theme-library-test-{}-{}",
+            std::process::id(),
+            test_name
+        ));
+        let _ = fs::remove_dir_all(&root);
+        let system = root.join("system");
+        fs::create_dir_all(&system).unwrap();
+        (
+            ThemeLibrary::with_dirs(root.join("user"), vec![system.clone()]),
+            system,
+        )
+    }
+
+    fn named(name: &str) -> Theme<palette::Srgba> {
+        let mut theme = Theme::dark_default();
+        theme.name = name.to_string();
+        theme
+    }
+
+    #[test]
+    fn test_save_load_list() {
+        let (library, _) = library("save_load_list");
+        library.save(&named("B"), Collision::Fail).unwrap();
+        library.save(&named("A"), Collision::Fail).unwrap();
+
+        let names: Vec<_> = library.themes().into_iter().map(|t| t.name).collect();
+        assert_eq!(names, ["A", "B"]);
+        assert_eq!(library.load("A").unwrap(), named("A"));
+        assert!(matches!(library.load("C"), Err(LibraryError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_collisions() {
+        let (library, _) = library("collisions");
+        library.save(&named("A"), Collision::Fail).unwrap();
+
+        assert!(matches!(
+            library.save(&named("A"), Collision::Fail),
+            Err(LibraryError::AlreadyExists(_))
+        ));
+        assert_eq!(
+            library.save(&named("A"), Collision::Rename).unwrap().name,
+            "A (2)"
+        );
+        assert_eq!(
+            library
+                .save(&named("A"), Collision::Overwrite)
+                .unwrap()
+                .name,
+            "A"
+        );
+        assert_eq!(library.themes().len(), 2);
+        assert!(matches!(
+            library.save(&named("../A"), Collision::Fail),
+            Err(LibraryError::InvalidName(_))
+        ));
+    }
+
+    #[test]
+    fn test_rename_delete() {
+        let (library, _) = library("rename_delete");
+        library.save(&named("A"), Collision::Fail).unwrap();
+
+        let renamed = library.rename("A", "B").unwrap();
+        assert_eq!(renamed.name, "B");
+        assert_eq!(library.load("B").unwrap().name, "B");
+        assert!(library.load("A").is_err());
+
+        library.delete("B").unwrap();
+        assert!(library.themes().is_empty());
+    }
+
+    #[test]
+    fn test_system_themes() {
+        let (library, system) = library("system_themes");
+        let ron = ron::ser::to_string_pretty(&named("S"), Default::default()).unwrap();
+        fs::write(system.join("S.ron"), ron).unwrap();
+
+        let info = library.info("S").unwrap();
+        assert_eq!(info.source, ThemeSource::System);
+        assert!(matches!(
+            library.delete("S"),
+            Err(LibraryError::ReadOnly(_))
+        ));
+
+        // user themes shadow system themes
+        library.save(&named("S"), Collision::Fail).unwrap();
+        assert_eq!(library.info("S").unwrap().source, ThemeSource::User);
+        assert_eq!(library.themes().len(), 1);
+    }
+}
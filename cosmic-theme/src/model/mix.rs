@@ -0,0 +1,44 @@
+use palette::{ClampAssign, FromColor, IntoColor, Mix, Oklaba, Srgba};
+
+use super::zip_theme;
+use crate::Theme;
+
+impl Theme<Srgba> {
+    /// interpolate the colors of two themes in Oklab
+    /// the resulting theme uses every other variable of `other`
+    pub fn mix(&self, other: &Self, factor: f32) -> Self {
+        let factor = factor.clamp(0.0, 1.0);
+        zip_theme(self, other, |a, b| {
+            let a: Oklaba = a.into_color();
+            let b: Oklaba = b.into_color();
+            let mut mixed = Srgba::from_color(a.mix(b, factor));
+            mixed.clamp_assign();
+            mixed
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Theme;
+
+    #[test]
+    fn test_mix_endpoints() {
+        let dark = Theme::dark_default();
+        let light = Theme::light_default();
+
+        let start = dark.mix(&light, 0.0);
+        let end = dark.mix(&light, 1.0);
+        let close = |a: palette::Srgba, b: palette::Srgba| {
+            (a.red - b.red).abs() < 0.001
+                && (a.green - b.green).abs() < 0.001
+                && (a.blue - b.blue).abs() < 0.001
+                && (a.alpha - b.alpha).abs() < 0.001
+        };
+
+        assert!(close(start.background.base, dark.background.base));
+        assert!(close(end.background.base, light.background.base));
+        assert!(close(end.accent.on, light.accent.on));
+        assert_eq!(end.is_dark, light.is_dark);
+    }
+}
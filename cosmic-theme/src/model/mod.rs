@@ -1,11 +1,29 @@
 pub use corner::*;
 pub use cosmic_palette::*;
 pub use derivation::*;
+pub use elevation::*;
+pub use layout::*;
+pub use library::*;
+pub use mode::*;
+pub use palette_config::*;
 pub use spacing::*;
 pub use theme::*;
+pub use translucency::*;
+pub use typography::*;
+
+pub(crate) use zip::zip_theme;
 
 mod corner;
 mod cosmic_palette;
 mod derivation;
+mod elevation;
+mod layout;
+mod library;
+mod mix;
+mod mode;
+mod palette_config;
 mod spacing;
 mod theme;
+mod translucency;
+mod typography;
+mod zip;
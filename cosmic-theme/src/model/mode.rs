@@ -0,0 +1,259 @@
+use std::f64::consts::PI;
+
+use cosmic_config::{
+    cosmic_config_derive::CosmicConfigEntry, Config, ConfigGet, ConfigSet, CosmicConfigEntry,
+};
+use palette::Srgba;
+use serde::{Deserialize, Serialize};
+
+use crate::{Theme, MODE_ID, NAME};
+
+/// Minutes in a day
+const MINUTES_PER_DAY: i32 = 24 * 60;
+
+/// Local date and time used to evaluate a theme schedule
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct LocalTime {
+    /// day of the year, starting at 1
+    pub day_of_year: u16,
+    /// minutes since local midnight
+    pub minute: u16,
+    /// offset of local time from UTC in minutes
+    pub utc_offset: i16,
+}
+
+/// Sunrise and sunset of a day in minutes since UTC midnight
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SunTimes {
+    /// the sun rises and sets
+    Daily {
+        /// minutes since UTC midnight of sunrise
+        sunrise: f64,
+        /// minutes since UTC midnight of sunset
+        sunset: f64,
+    },
+    /// the sun does not set
+    PolarDay,
+    /// the sun does not rise
+    PolarNight,
+}
+
+impl SunTimes {
+    /// compute sunrise and sunset for a day of the year at a location
+    /// uses the NOAA approximation, which is accurate to within a few minutes
+    pub fn new(day_of_year: u16, latitude: f64, longitude: f64) -> Self {
+        let gamma = 2.0 * PI / 365.0 * (f64::from(day_of_year) - 1.0);
+        let eqtime = 229.18
+            * (0.000075 + 0.001868 * gamma.cos()
+                - 0.032077 * gamma.sin()
+                - 0.014615 * (2.0 * gamma).cos()
+                - 0.040849 * (2.0 * gamma).sin());
+        let decl = 0.006918 - 0.399912 * gamma.cos() + 0.070257 * gamma.sin()
+            - 0.006758 * (2.0 * gamma).cos()
+            + 0.000907 * (2.0 * gamma).sin()
+            - 0.002697 * (3.0 * gamma).cos()
+            + 0.00148 * (3.0 * gamma).sin();
+
+        let latitude = latitude.to_radians();
+        let cos_ha = 90.833_f64.to_radians().cos() / (latitude.cos() * decl.cos())
+            - latitude.tan() * decl.tan();
+
+        if cos_ha > 1.0 {
+            return Self::PolarNight;
+        } else if cos_ha < -1.0 {
+            return Self::PolarDay;
+        }
+
+        let ha = cos_ha.acos().to_degrees();
+        Self::Daily {
+            sunrise: 720.0 - 4.0 * (longitude + ha) - eqtime,
+            sunset: 720.0 - 4.0 * (longitude - ha) - eqtime,
+        }
+    }
+}
+
+/// Schedule for switching between the light and dark themes
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+pub enum ThemeSchedule {
+    /// switch at fixed local times
+    Fixed {
+        /// minutes since local midnight at which the light theme is activated
+        light: u16,
+        /// minutes since local midnight at which the dark theme is activated
+        dark: u16,
+    },
+    /// switch at sunrise and sunset, computed from a location
+    Sun {
+        /// latitude in degrees, positive to the north
+        latitude: f64,
+        /// longitude in degrees, positive to the east
+        longitude: f64,
+    },
+}
+
+impl Default for ThemeSchedule {
+    fn default() -> Self {
+        Self::Fixed {
+            light: 7 * 60,
+            dark: 19 * 60,
+        }
+    }
+}
+
+impl ThemeSchedule {
+    /// whether the dark theme should be active at a local time
+    pub fn is_dark_at(&self, time: LocalTime) -> bool {
+        let minute = i32::from(time.minute);
+        match *self {
+            Self::Fixed { light, dark } => !in_range(minute, i32::from(light), i32::from(dark)),
+            Self::Sun {
+                latitude,
+                longitude,
+            } => match SunTimes::new(time.day_of_year, latitude, longitude) {
+                SunTimes::Daily { sunrise, sunset } => {
+                    let offset = f64::from(time.utc_offset);
+                    let local =
+                        |utc: f64| ((utc + offset).round() as i32).rem_euclid(MINUTES_PER_DAY);
+                    !in_range(minute, local(sunrise), local(sunset))
+                }
+                SunTimes::PolarDay => false,
+                SunTimes::PolarNight => true,
+            },
+        }
+    }
+}
+
+/// whether a minute of the day lies within a range which may wrap around midnight
+fn in_range(minute: i32, start: i32, end: i32) -> bool {
+    if start <= end {
+        start <= minute && minute < end
+    } else {
+        minute >= start || minute < end
+    }
+}
+
+/// Light and dark mode of the Cosmic theme
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, CosmicConfigEntry)]
+pub struct ThemeMode {
+    /// whether the dark theme is active
+    pub is_dark: bool,
+    /// whether to switch between the light and dark themes on a schedule
+    pub auto_switch: bool,
+    /// schedule used when switching automatically
+    pub schedule: ThemeSchedule,
+}
+
+impl Default for ThemeMode {
+    fn default() -> Self {
+        Self {
+            is_dark: true,
+            auto_switch: false,
+            schedule: ThemeSchedule::default(),
+        }
+    }
+}
+
+impl ThemeMode {
+    /// version of the theme mode
+    pub fn version() -> u64 {
+        1
+    }
+
+    /// get the config for the theme mode
+    pub fn config() -> Result<Config, cosmic_config::Error> {
+        Config::new(MODE_ID, Self::version())
+    }
+
+    /// update the mode from its schedule, returning whether it changed
+    pub fn update(&mut self, time: LocalTime) -> bool {
+        if !self.auto_switch {
+            return false;
+        }
+
+        let is_dark = self.schedule.is_dark_at(time);
+        let changed = is_dark != self.is_dark;
+        self.is_dark = is_dark;
+        changed
+    }
+
+    /// write the theme of the current mode to the active theme config
+    pub fn apply(&self) -> Result<Theme<Srgba>, cosmic_config::Error> {
+        let (config, default) = if self.is_dark {
+            (Theme::<Srgba>::dark_config()?, Theme::dark_default())
+        } else {
+            (Theme::<Srgba>::light_config()?, Theme::light_default())
+        };
+
+        let theme = match Theme::<Srgba>::get_entry(&config) {
+            Ok(theme) => theme,
+            // modes which have never been customized use the built in theme
+            Err((errors, _)) if errors.iter().all(is_not_found) => default,
+            // partially written themes keep the fields which could be read
+            Err((_, theme)) => theme,
+        };
+        theme.write_entry(&Config::new(NAME, Theme::<Srgba>::version())?)?;
+        Ok(theme)
+    }
+}
+
+/// whether a config error is caused by a missing entry
+fn is_not_found(err: &cosmic_config::Error) -> bool {
+    matches!(err, cosmic_config::Error::Io(err) if err.kind() == std::io::ErrorKind::NotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LocalTime, SunTimes, ThemeMode, ThemeSchedule};
+
+    fn at(day_of_year: u16, hour: u16, utc_offset: i16) -> LocalTime {
+        LocalTime {
+            day_of_year,
+            minute: hour * 60,
+            utc_offset,
+        }
+    }
+
+    #[test]
+    fn test_fixed_schedule() {
+        let schedule = ThemeSchedule::default();
+        assert!(schedule.is_dark_at(at(1, 3, 0)));
+        assert!(!schedule.is_dark_at(at(1, 12, 0)));
+        assert!(schedule.is_dark_at(at(1, 22, 0)));
+
+        // dark during the day
+        let schedule = ThemeSchedule::Fixed {
+            light: 20 * 60,
+            dark: 8 * 60,
+        };
+        assert!(!schedule.is_dark_at(at(1, 3, 0)));
+        assert!(schedule.is_dark_at(at(1, 12, 0)));
+    }
+
+    #[test]
+    fn test_sun_schedule() {
+        // Denver, UTC-7 in winter
+        let schedule = ThemeSchedule::Sun {
+            latitude: 39.74,
+            longitude: -104.99,
+        };
+        assert!(schedule.is_dark_at(at(15, 6, -7 * 60)));
+        assert!(!schedule.is_dark_at(at(15, 12, -7 * 60)));
+        assert!(schedule.is_dark_at(at(15, 18, -7 * 60)));
+
+        // Tromsø in midwinter and midsummer
+        assert_eq!(SunTimes::new(355, 69.65, 18.96), SunTimes::PolarNight);
+        assert_eq!(SunTimes::new(172, 69.65, 18.96), SunTimes::PolarDay);
+    }
+
+    #[test]
+    fn test_update() {
+        let mut mode = ThemeMode::default();
+        assert!(!mode.update(at(1, 12, 0)));
+        assert!(mode.is_dark);
+
+        mode.auto_switch = true;
+        assert!(mode.update(at(1, 12, 0)));
+        assert!(!mode.is_dark);
+        assert!(!mode.update(at(1, 13, 0)));
+    }
+}
@@ -0,0 +1,271 @@
+use std::{fmt, io};
+
+use cosmic_config::{Config, ConfigGet};
+use palette::Srgba;
+use ron::Value;
+
+use crate::{
+    util::CssColor, CosmicPalette, CosmicPaletteInner, DARK_PALETTE, LIGHT_PALETTE, PALETTE_ID,
+};
+
+/// key of the dark palette in the palette config
+pub const DARK_PALETTE_KEY: &str = "dark";
+/// key of the light palette in the palette config
+pub const LIGHT_PALETTE_KEY: &str = "light";
+
+/// Error loading a palette from the palette config
+#[derive(Debug)]
+pub enum PaletteError {
+    /// failed to read the palette config
+    Config(cosmic_config::Error),
+    /// the palette overrides are not a struct of colors
+    InvalidOverrides,
+    /// the neutral colors do not get lighter in dark palettes, or darker in light palettes
+    NeutralOrder(usize),
+    /// failed to parse a color of the palette
+    Ron(ron::Error),
+    /// the palette overrides contain a field which is not part of a palette
+    UnknownField(String),
+}
+
+impl fmt::Display for PaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Config(err) => err.fmt(f),
+            Self::InvalidOverrides => write!(f, "palette overrides must be a struct of colors"),
+            Self::NeutralOrder(i) => write!(
+                f,
+                "neutral_{} does not continue the ramp from neutral_{}",
+                i,
+                i - 1
+            ),
+            Self::Ron(err) => err.fmt(f),
+            Self::UnknownField(field) => write!(f, "unknown palette field '{}'", field),
+        }
+    }
+}
+
+impl std::error::Error for PaletteError {}
+
+impl From<cosmic_config::Error> for PaletteError {
+    fn from(f: cosmic_config::Error) -> Self {
+        Self::Config(f)
+    }
+}
+
+impl From<ron::Error> for PaletteError {
+    fn from(f: ron::Error) -> Self {
+        Self::Ron(f)
+    }
+}
+
+impl CosmicPalette<CssColor> {
+    /// version of the palette config
+    pub fn version() -> u64 {
+        1
+    }
+
+    /// get the config for the palettes
+    ///
+    /// Each palette key holds overrides for the colors of the built in palette, which are read
+    /// from the user config and then from the system config in `/usr/share/cosmic`, so that
+    /// vendors can ship their own palettes and users can replace them.
+    pub fn config() -> Result<Config, cosmic_config::Error> {
+        Config::new(PALETTE_ID, Self::version())
+    }
+
+    /// get the active dark palette, or the built in palette if the config is missing or invalid
+    pub fn active_dark() -> Self {
+        Self::active(true)
+    }
+
+    /// get the active light palette, or the built in palette if the config is missing or invalid
+    pub fn active_light() -> Self {
+        Self::active(false)
+    }
+
+    fn active(is_dark: bool) -> Self {
+        Self::config()
+            .map_err(PaletteError::from)
+            .and_then(|config| Self::load(&config, is_dark))
+            .unwrap_or_else(|_| builtin(is_dark).clone())
+    }
+
+    /// load a palette from a config by applying its overrides to the built in palette
+    pub fn load(config: &Config, is_dark: bool) -> Result<Self, PaletteError> {
+        let key = if is_dark {
+            DARK_PALETTE_KEY
+        } else {
+            LIGHT_PALETTE_KEY
+        };
+
+        let overrides = match config.get::<Value>(key) {
+            Ok(overrides) => overrides,
+            Err(cosmic_config::Error::Io(err)) if err.kind() == io::ErrorKind::NotFound => {
+                return Ok(builtin(is_dark).clone());
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let inner = apply_overrides(builtin(is_dark).as_ref(), overrides)?;
+        let palette = if is_dark {
+            Self::Dark(inner)
+        } else {
+            Self::Light(inner)
+        };
+        palette.validate()?;
+        Ok(palette)
+    }
+
+    /// check that the neutral colors form a ramp from the background to the foreground
+    pub fn validate(&self) -> Result<(), PaletteError> {
+        let p = self.as_ref();
+        let luminance = |c: &CssColor| {
+            let c: Srgba = c.clone().into();
+            let c = c.color.into_linear();
+            0.2126 * c.red + 0.7152 * c.green + 0.0722 * c.blue
+        };
+        let neutrals = [
+            &p.neutral_0,
+            &p.neutral_1,
+            &p.neutral_2,
+            &p.neutral_3,
+            &p.neutral_4,
+            &p.neutral_5,
+            &p.neutral_6,
+            &p.neutral_7,
+            &p.neutral_8,
+            &p.neutral_9,
+            &p.neutral_10,
+        ]
+        .map(luminance);
+
+        for i in 1..neutrals.len() {
+            let (previous, current) = (neutrals[i - 1], neutrals[i]);
+            let ordered = if self.is_dark() {
+                current > previous
+            } else {
+                current < previous
+            };
+            if !ordered {
+                return Err(PaletteError::NeutralOrder(i));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn builtin(is_dark: bool) -> &'static CosmicPalette<CssColor> {
+    if is_dark {
+        &DARK_PALETTE
+    } else {
+        &LIGHT_PALETTE
+    }
+}
+
+/// replace the fields of a palette with those of a struct of overrides
+fn apply_overrides(
+    palette: &CosmicPaletteInner<CssColor>,
+    overrides: Value,
+) -> Result<CosmicPaletteInner<CssColor>, PaletteError> {
+    let Value::Map(overrides) = overrides else {
+        return Err(PaletteError::InvalidOverrides);
+    };
+    let Value::Map(mut fields) = ron::from_str::<Value>(&ron::to_string(palette)?)
+        .map_err(|err| PaletteError::Ron(err.code))?
+    else {
+        return Err(PaletteError::InvalidOverrides);
+    };
+
+    for (key, value) in overrides.iter() {
+        if !fields.keys().any(|k| k == key) {
+            let field = match key {
+                Value::String(key) => key.clone(),
+                key => format!("{:?}", key),
+            };
+            return Err(PaletteError::UnknownField(field));
+        }
+        fields.insert(key.clone(), value.clone());
+    }
+
+    Ok(Value::Map(fields).into_rust()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PaletteError, DARK_PALETTE_KEY, LIGHT_PALETTE_KEY};
+    use crate::{util::CssColor, CosmicPalette, DARK_PALETTE, PALETTE_ID};
+    use cosmic_config::Config;
+    use palette::Srgba;
+
+    fn config(test_name: &str) -> (Config, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!(
+            "cosmic-theme-test-{}-{}",
+            std::process::id(),
+            test_name
+        ));
+        let config = Config::with_custom_path(
+            PALETTE_ID,
+            CosmicPalette::<CssColor>::version(),
+            path.clone(),
+        )
+        .unwrap();
+        (config, path)
+    }
+
+    fn write(path: &std::path::Path, key: &str, ron: &str) {
+        let dir = path
+            .join(PALETTE_ID)
+            .join(format!("v{}", CosmicPalette::<CssColor>::version()));
+        std::fs::write(dir.join(key), ron).unwrap();
+    }
+
+    #[test]
+    fn test_fallback() {
+        let (config, path) = config("palette-fallback");
+        let palette = CosmicPalette::load(&config, true).unwrap();
+        let _ = std::fs::remove_dir_all(path);
+        assert_eq!(palette, *DARK_PALETTE);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let (config, path) = config("palette-overrides");
+        write(
+            &path,
+            DARK_PALETTE_KEY,
+            r##"(blue: (c: "#0000FF"), name: "vendor")"##,
+        );
+        write(&path, LIGHT_PALETTE_KEY, r##"(bleu: (c: "#0000FF"))"##);
+        let dark = CosmicPalette::load(&config, true);
+        let light = CosmicPalette::load(&config, false);
+        let _ = std::fs::remove_dir_all(path);
+
+        let dark = dark.unwrap();
+        assert!(dark.is_dark());
+        let blue: Srgba = dark.as_ref().blue.clone().into();
+        assert_eq!(blue, Srgba::new(0.0, 0.0, 1.0, 1.0));
+        assert_eq!(dark.as_ref().name, "vendor");
+        assert_eq!(dark.as_ref().red, DARK_PALETTE.as_ref().red);
+
+        assert!(matches!(light, Err(PaletteError::UnknownField(f)) if f == "bleu"));
+    }
+
+    #[test]
+    fn test_validate() {
+        let (config, path) = config("palette-validate");
+        write(&path, DARK_PALETTE_KEY, r##"(neutral_3: (c: "#FFFFFF"))"##);
+        write(
+            &path,
+            LIGHT_PALETTE_KEY,
+            r##"(neutral_3: (c: "not a color"))"##,
+        );
+        let dark = CosmicPalette::load(&config, true);
+        let light = CosmicPalette::load(&config, false);
+        let _ = std::fs::remove_dir_all(path);
+
+        assert!(matches!(dark, Err(PaletteError::NeutralOrder(4))));
+        assert!(matches!(light, Err(PaletteError::Ron(_))));
+    }
+}
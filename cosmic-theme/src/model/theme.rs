@@ -1,8 +1,11 @@
 use crate::{
-    steps::*, Component, Container, CornerRadii, CosmicPalette, CosmicPaletteInner, Spacing,
-    DARK_PALETTE, LIGHT_PALETTE, NAME,
+    steps::*, Component, Container, CornerRadii, CosmicPalette, CosmicPaletteInner, Density,
+    Elevation, Layout, Spacing, Translucency, Typography, DARK_PALETTE, DARK_THEME_BUILDER_ID,
+    DARK_THEME_ID, LIGHT_PALETTE, LIGHT_THEME_BUILDER_ID, LIGHT_THEME_ID, NAME,
+};
+use cosmic_config::{
+    cosmic_config_derive::CosmicConfigEntry, Config, ConfigGet, ConfigSet, CosmicConfigEntry,
 };
-use cosmic_config::{Config, ConfigGet, ConfigSet, CosmicConfigEntry};
 use palette::{IntoColor, Srgb, Srgba};
 use serde::{Deserialize, Serialize};
 use std::num::NonZeroUsize;
@@ -20,7 +23,7 @@ pub enum Layer {
 }
 
 /// Cosmic Theme data structure with all colors and its name
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, CosmicConfigEntry)]
 pub struct Theme<C> {
     /// name of the theme
     pub name: String,
@@ -56,100 +59,24 @@ pub struct Theme<C> {
     pub spacing: Spacing,
     /// corner radii
     pub corner_radii: CornerRadii,
+    /// widget layout
+    #[serde(default)]
+    pub layout: Layout,
+    /// typography
+    #[serde(default)]
+    pub typography: Typography,
+    /// elevation shadows
+    #[serde(default)]
+    pub elevation: Elevation<C>,
+    /// translucency of surfaces
+    #[serde(default)]
+    pub translucency: Translucency,
     /// is dark
     pub is_dark: bool,
     /// is high contrast
     pub is_high_contrast: bool,
 }
 
-impl CosmicConfigEntry for Theme<Srgba> {
-    fn write_entry(&self, config: &Config) -> Result<(), cosmic_config::Error> {
-        let self_ = self.clone();
-        // TODO do as transaction
-        let tx = config.transaction();
-
-        tx.set("name", self_.name)?;
-        tx.set("background", self_.background)?;
-        tx.set("primary", self_.primary)?;
-        tx.set("secondary", self_.secondary)?;
-        tx.set("accent", self_.accent)?;
-        tx.set("success", self_.success)?;
-        tx.set("destructive", self_.destructive)?;
-        tx.set("warning", self_.warning)?;
-        tx.set("palette", self_.palette)?;
-        tx.set("is_dark", self_.is_dark)?;
-        tx.set("is_high_contrast", self_.is_high_contrast)?;
-        tx.set("spacing", self_.spacing)?;
-        tx.set("corner_radii", self_.corner_radii)?;
-
-        tx.commit()
-    }
-
-    fn get_entry(config: &Config) -> Result<Self, (Vec<cosmic_config::Error>, Self)> {
-        let mut default = Self::default();
-        let mut errors = Vec::new();
-
-        match config.get::<String>("name") {
-            Ok(name) => default.name = name,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<Container<Srgba>>("background") {
-            Ok(background) => default.background = background,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<Container<Srgba>>("primary") {
-            Ok(primary) => default.primary = primary,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<Container<Srgba>>("secondary") {
-            Ok(secondary) => default.secondary = secondary,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<Component<Srgba>>("accent") {
-            Ok(accent) => default.accent = accent,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<Component<Srgba>>("success") {
-            Ok(success) => default.success = success,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<Component<Srgba>>("destructive") {
-            Ok(destructive) => default.destructive = destructive,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<Component<Srgba>>("warning") {
-            Ok(warning) => default.warning = warning,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<CosmicPaletteInner<Srgba>>("palette") {
-            Ok(palette) => default.palette = palette,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<bool>("is_dark") {
-            Ok(is_dark) => default.is_dark = is_dark,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<bool>("is_high_contrast") {
-            Ok(is_high_contrast) => default.is_high_contrast = is_high_contrast,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<Spacing>("spacing") {
-            Ok(spacing) => default.spacing = spacing,
-            Err(e) => errors.push(e),
-        }
-        match config.get::<CornerRadii>("corner_radii") {
-            Ok(corner_radii) => default.corner_radii = corner_radii,
-            Err(e) => errors.push(e),
-        }
-
-        if errors.is_empty() {
-            Ok(default)
-        } else {
-            Err((errors, default))
-        }
-    }
-}
-
 impl Default for Theme<Srgba> {
     fn default() -> Self {
         Self::dark_default()
@@ -172,6 +99,16 @@ impl<C> Theme<C> {
     pub fn id() -> &'static str {
         NAME
     }
+
+    /// get the config for the dark theme
+    pub fn dark_config() -> Result<Config, cosmic_config::Error> {
+        Config::new(DARK_THEME_ID, Self::version())
+    }
+
+    /// get the config for the light theme
+    pub fn light_config() -> Result<Config, cosmic_config::Error> {
+        Config::new(LIGHT_THEME_ID, Self::version())
+    }
 }
 
 impl Theme<Srgba> {
@@ -406,6 +343,26 @@ impl Theme<Srgba> {
     pub fn radius_xl(&self) -> [f32; 4] {
         self.corner_radii.radius_xl
     }
+
+    /// get the layout density
+    pub fn density(&self) -> Density {
+        self.layout.density
+    }
+
+    /// get the translucency of surfaces, which is always opaque for high contrast themes
+    pub fn translucency(&self) -> Translucency {
+        self.translucency.clamped(self.is_high_contrast)
+    }
+
+    /// get the opacity of containers on a layer
+    pub fn layer_opacity(&self, layer: Layer) -> f32 {
+        self.translucency().layer(layer)
+    }
+
+    /// get the opacity of the window background
+    pub fn window_opacity(&self) -> f32 {
+        self.translucency().window
+    }
 }
 
 impl<C> From<CosmicPalette<C>> for Theme<Srgba>
@@ -418,11 +375,19 @@ where
 }
 
 /// Helper for building customized themes
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, CosmicConfigEntry)]
 pub struct ThemeBuilder {
     palette: CosmicPalette<Srgba>,
     spacing: Spacing,
     corner_radii: CornerRadii,
+    #[serde(default)]
+    density: Density,
+    #[serde(default)]
+    typography: Typography,
+    #[serde(default)]
+    translucency: Translucency,
+    derivation: Derivation,
+    ramp_length: usize,
     neutral_tint: Option<Srgb>,
     bg_color: Option<Srgba>,
     primary_container_bg: Option<Srgba>,
@@ -440,6 +405,11 @@ impl Default for ThemeBuilder {
             palette: DARK_PALETTE.to_owned().into(),
             spacing: Spacing::default(),
             corner_radii: CornerRadii::default(),
+            density: Density::default(),
+            typography: Typography::default(),
+            translucency: Translucency::default(),
+            derivation: Derivation::default(),
+            ramp_length: 100,
             neutral_tint: Default::default(),
             text_tint: Default::default(),
             bg_color: Default::default(),
@@ -454,6 +424,53 @@ impl Default for ThemeBuilder {
 }
 
 impl ThemeBuilder {
+    /// version of the theme builder
+    pub fn version() -> u64 {
+        1
+    }
+
+    /// get the config for the dark theme builder
+    pub fn dark_config() -> Result<Config, cosmic_config::Error> {
+        Config::new(DARK_THEME_BUILDER_ID, Self::version())
+    }
+
+    /// get the config for the light theme builder
+    pub fn light_config() -> Result<Config, cosmic_config::Error> {
+        Config::new(LIGHT_THEME_BUILDER_ID, Self::version())
+    }
+
+    /// Build the theme and write it to the provided theme config
+    pub fn apply(self, theme_config: &Config) -> Result<Theme<Srgba>, cosmic_config::Error> {
+        let theme = self.build();
+        theme.write_entry(theme_config)?;
+        Ok(theme)
+    }
+
+    /// Watch the builder config, rebuilding the theme and writing it to the theme config
+    /// whenever the builder changes.
+    /// Fields of the builder which fail to load, and failures to write the theme, are passed to
+    /// `on_error`.
+    /// The theme is only rewritten while the returned watcher is alive.
+    pub fn watch(
+        builder_config: &Config,
+        theme_config: Config,
+        on_error: impl Fn(cosmic_config::Error) + Send + Sync + 'static,
+    ) -> Result<notify::RecommendedWatcher, cosmic_config::Error> {
+        builder_config.watch(move |builder_config, _keys| {
+            // partially written builders still produce a usable theme
+            let builder = match Self::get_entry(builder_config) {
+                Ok(builder) => builder,
+                Err((errors, builder)) => {
+                    errors.into_iter().for_each(&on_error);
+                    builder
+                }
+            };
+            if let Err(err) = builder.apply(&theme_config) {
+                on_error(err);
+            }
+        })
+    }
+
     /// Get a builder that is initialized with the default dark theme
     pub fn dark() -> Self {
         Self {
@@ -470,11 +487,35 @@ impl ThemeBuilder {
         }
     }
 
+    /// Get a builder that is initialized with the active dark palette of the palette config
+    pub fn active_dark() -> Self {
+        Self::palette(CosmicPalette::active_dark().into())
+    }
+
+    /// Get a builder that is initialized with the active light palette of the palette config
+    pub fn active_light() -> Self {
+        Self::palette(CosmicPalette::active_light().into())
+    }
+
+    /// Get a builder that is initialized with the active dark palette of the palette config, in
+    /// high contrast
+    pub fn active_dark_high_contrast() -> Self {
+        let palette: CosmicPalette<Srgba> = CosmicPalette::active_dark().into();
+        Self::palette(CosmicPalette::HighContrastDark(palette.inner()))
+    }
+
+    /// Get a builder that is initialized with the active light palette of the palette config, in
+    /// high contrast
+    pub fn active_light_high_contrast() -> Self {
+        let palette: CosmicPalette<Srgba> = CosmicPalette::active_light().into();
+        Self::palette(CosmicPalette::HighContrastLight(palette.inner()))
+    }
+
     /// Get a builder that is initialized with the default dark high contrast theme
     pub fn dark_high_contrast() -> Self {
         let palette: CosmicPalette<Srgba> = DARK_PALETTE.to_owned().into();
         Self {
-            palette: CosmicPalette::HighContrastLight(palette.inner()),
+            palette: CosmicPalette::HighContrastDark(palette.inner()),
             ..Default::default()
         }
     }
@@ -508,6 +549,36 @@ impl ThemeBuilder {
         self
     }
 
+    /// set the layout density of the builder
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// set the typography of the builder
+    pub fn typography(mut self, typography: Typography) -> Self {
+        self.typography = typography;
+        self
+    }
+
+    /// set the translucency of surfaces, which is ignored by high contrast palettes
+    pub fn translucency(mut self, translucency: Translucency) -> Self {
+        self.translucency = translucency;
+        self
+    }
+
+    /// set the strategy used to derive colors from the palette
+    pub fn derivation(mut self, derivation: Derivation) -> Self {
+        self.derivation = derivation;
+        self
+    }
+
+    /// set the number of colors in the ramps used to derive colors, which is at least 2
+    pub fn ramp_length(mut self, ramp_length: usize) -> Self {
+        self.ramp_length = ramp_length;
+        self
+    }
+
     /// apply a neutral tint to the palette
     pub fn neutral_tint(mut self, tint: Srgb) -> Self {
         self.neutral_tint = Some(tint);
@@ -562,6 +633,11 @@ impl ThemeBuilder {
             mut palette,
             spacing,
             corner_radii,
+            density,
+            typography,
+            translucency,
+            derivation,
+            ramp_length,
             neutral_tint,
             text_tint,
             bg_color,
@@ -575,6 +651,8 @@ impl ThemeBuilder {
 
         let is_dark = palette.is_dark();
         let is_high_contrast = palette.is_high_contrast();
+        let spacing = density.spacing(spacing);
+        let corner_radii = density.corner_radii(corner_radii);
 
         let accent = if let Some(accent) = accent {
             accent.into_color()
@@ -600,10 +678,12 @@ impl ThemeBuilder {
             palette.as_ref().red.to_owned()
         };
 
-        let text_steps_array = text_tint.map(|c| steps(c, NonZeroUsize::new(100).unwrap()));
+        let ramp_length = NonZeroUsize::new(ramp_length.max(2)).unwrap();
+        let text_steps_array = text_tint.map(|c| derivation.steps(c.into(), ramp_length));
 
         if let Some(neutral_tint) = neutral_tint {
-            let mut neutral_steps_arr = steps(neutral_tint, NonZeroUsize::new(11).unwrap());
+            let mut neutral_steps_arr =
+                derivation.steps(neutral_tint.into(), NonZeroUsize::new(11).unwrap());
             if !is_dark {
                 neutral_steps_arr.reverse();
             }
@@ -629,9 +709,9 @@ impl ThemeBuilder {
         } else {
             p_ref.gray_1.clone()
         };
-        let step_array = steps(bg, NonZeroUsize::new(100).unwrap());
+        let step_array = derivation.steps(bg, ramp_length);
 
-        let bg_index = color_index(bg, step_array.len());
+        let bg_index = derivation.index(bg, step_array.len());
         let primary_container_bg = if let Some(primary_container_bg_color) = primary_container_bg {
             primary_container_bg_color
         } else {
@@ -646,11 +726,11 @@ impl ThemeBuilder {
 
         let bg_component = get_surface_color(bg_index, 8, &step_array, is_dark, &p_ref.neutral_2);
         let on_bg_component = get_text(
-            color_index(bg_component, step_array.len()),
+            derivation.index(bg_component, step_array.len()),
             &step_array,
             is_dark,
             &p_ref.neutral_8,
-            text_steps_array.as_ref(),
+            text_steps_array.as_deref(),
         );
         let bg_component = Component::component(
             bg_component,
@@ -661,15 +741,15 @@ impl ThemeBuilder {
             p_ref.neutral_8,
         );
 
-        let primary_index = color_index(primary_container_bg, step_array.len());
+        let primary_index = derivation.index(primary_container_bg, step_array.len());
         let primary_component =
             get_surface_color(primary_index, 6, &step_array, is_dark, &p_ref.neutral_3);
         let on_primary_component = get_text(
-            color_index(primary_component, step_array.len()),
+            derivation.index(primary_component, step_array.len()),
             &step_array,
             is_dark,
             &p_ref.neutral_8,
-            text_steps_array.as_ref(),
+            text_steps_array.as_deref(),
         );
         let primary_component = Component::component(
             primary_component,
@@ -680,15 +760,15 @@ impl ThemeBuilder {
             p_ref.neutral_8,
         );
 
-        let secondary_index = color_index(secondary_container_bg, step_array.len());
+        let secondary_index = derivation.index(secondary_container_bg, step_array.len());
         let secondary_component =
             get_surface_color(secondary_index, 3, &step_array, is_dark, &p_ref.neutral_4);
         let on_secondary_component = get_text(
-            color_index(secondary_component, step_array.len()),
+            derivation.index(secondary_component, step_array.len()),
             &step_array,
             is_dark,
             &p_ref.neutral_10,
-            text_steps_array.as_ref(),
+            text_steps_array.as_deref(),
         );
         let secondary_component = Component::component(
             secondary_component,
@@ -713,6 +793,13 @@ impl ThemeBuilder {
         let mut button_disabled_border = button_border;
         button_disabled_border.alpha *= 0.5;
 
+        let shadow = if is_dark {
+            p_ref.neutral_0
+        } else {
+            p_ref.neutral_10
+        };
+        let elevation = Elevation::new(shadow, p_ref.neutral_8, is_dark, is_high_contrast);
+
         let mut text_button = Component::component(
             Srgba::new(0.0, 0.0, 0.0, 0.0),
             p_ref.neutral_10,
@@ -741,7 +828,7 @@ impl ThemeBuilder {
                     &step_array,
                     is_dark,
                     &p_ref.neutral_8,
-                    text_steps_array.as_ref(),
+                    text_steps_array.as_deref(),
                 ),
             ),
             primary: Container::new(
@@ -752,7 +839,7 @@ impl ThemeBuilder {
                     &step_array,
                     is_dark,
                     &p_ref.neutral_8,
-                    text_steps_array.as_ref(),
+                    text_steps_array.as_deref(),
                 ),
             ),
             secondary: Container::new(
@@ -763,7 +850,7 @@ impl ThemeBuilder {
                     &step_array,
                     is_dark,
                     &p_ref.neutral_8,
-                    text_steps_array.as_ref(),
+                    text_steps_array.as_deref(),
                 ),
             ),
             accent: Component::colored_component(
@@ -822,6 +909,10 @@ impl ThemeBuilder {
             palette: palette.inner(),
             spacing,
             corner_radii,
+            layout: density.into(),
+            typography,
+            elevation,
+            translucency: translucency.clamped(is_high_contrast),
             is_dark,
             is_high_contrast,
         };
@@ -830,3 +921,195 @@ impl ThemeBuilder {
         theme
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Derivation, Layer, Theme, ThemeBuilder};
+    use crate::Translucency;
+    use cosmic_config::{Config, CosmicConfigEntry};
+    use palette::{Srgb, Srgba};
+
+    fn round_trip(test_name: &str, theme: Theme<Srgba>) {
+        let path = std::env::temp_dir().join(format!(
+            "cosmic-theme-test-{}-{}",
+            std::process::id(),
+            test_name
+        ));
+        let config =
+            Config::with_custom_path(super::NAME, Theme::<Srgba>::version(), path.clone()).unwrap();
+
+        theme.write_entry(&config).unwrap();
+        let loaded = Theme::<Srgba>::get_entry(&config).unwrap();
+        let _ = std::fs::remove_dir_all(path);
+
+        assert_eq!(theme, loaded);
+    }
+
+    #[test]
+    fn test_dark_round_trip() {
+        round_trip("dark", Theme::dark_default());
+    }
+
+    #[test]
+    fn test_light_round_trip() {
+        round_trip("light", Theme::light_default());
+    }
+
+    #[test]
+    fn test_high_contrast_dark_round_trip() {
+        round_trip("high-contrast-dark", Theme::high_contrast_dark_default());
+    }
+
+    #[test]
+    fn test_high_contrast_light_round_trip() {
+        round_trip("high-contrast-light", Theme::high_contrast_light_default());
+    }
+
+    /// remove top level fields from a pretty printed struct
+    fn without_fields(source: &str, fields: &[&str]) -> String {
+        let mut skipping = false;
+        source
+            .lines()
+            .filter(|line| {
+                let field = line.strip_prefix("    ").unwrap_or_default();
+                if field.starts_with(char::is_alphabetic) || !line.starts_with("    ") {
+                    skipping = fields.iter().any(|f| field.starts_with(&format!("{f}:")));
+                }
+                !skipping
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        // themes and builders written before these fields existed still load
+        let theme = Theme::dark_default();
+        let source = ron::ser::to_string_pretty(&theme, Default::default()).unwrap();
+        let source = without_fields(
+            &source,
+            &["layout", "typography", "elevation", "translucency"],
+        );
+        assert!(!source.contains("layout:"));
+        let loaded: Theme<Srgba> = ron::from_str(&source).unwrap();
+        assert_eq!(loaded.layout, Default::default());
+        assert_eq!(loaded.typography, Default::default());
+        assert_eq!(loaded.elevation, Default::default());
+        assert_eq!(loaded.translucency, Default::default());
+        assert_eq!(loaded.palette, theme.palette);
+
+        let builder = ThemeBuilder::dark();
+        let source = ron::ser::to_string_pretty(&builder, Default::default()).unwrap();
+        let source = without_fields(&source, &["density", "typography", "translucency"]);
+        assert!(!source.contains("density:"));
+        let loaded: ThemeBuilder = ron::from_str(&source).unwrap();
+        assert_eq!(loaded, builder);
+    }
+
+    #[test]
+    fn test_custom_round_trip() {
+        let theme = ThemeBuilder::light()
+            .accent(Srgb::new(0.8, 0.2, 0.4))
+            .neutral_tint(Srgb::new(0.3, 0.4, 0.6))
+            .text_tint(Srgb::new(0.1, 0.3, 0.2))
+            .bg_color(Srgba::new(0.9, 0.85, 0.8, 1.0))
+            .build();
+        round_trip("custom", theme);
+    }
+
+    fn contrast(a: Srgba, b: Srgba) -> f32 {
+        let luminance = |c: Srgba| {
+            let c = c.color.into_linear();
+            0.2126 * c.red + 0.7152 * c.green + 0.0722 * c.blue
+        };
+        let (a, b) = (luminance(a), luminance(b));
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    /// lowest contrast of text on the containers and components of a theme
+    fn min_text_contrast(theme: &Theme<Srgba>) -> f32 {
+        [&theme.background, &theme.primary, &theme.secondary]
+            .into_iter()
+            .flat_map(|c| {
+                [
+                    contrast(c.on, c.base),
+                    contrast(c.component.on, c.component.base),
+                ]
+            })
+            .fold(f32::MAX, f32::min)
+    }
+
+    #[test]
+    fn test_default_contrast() {
+        for derivation in [Derivation::Oklch, Derivation::TunedOklch, Derivation::Tonal] {
+            for ramp_length in [100, 50] {
+                for builder in [ThemeBuilder::dark(), ThemeBuilder::light()] {
+                    let theme = builder
+                        .derivation(derivation)
+                        .ramp_length(ramp_length)
+                        .build();
+                    let contrast = min_text_contrast(&theme);
+                    assert!(
+                        contrast >= 4.5,
+                        "{} {:?} {}: {}",
+                        theme.name,
+                        derivation,
+                        ramp_length,
+                        contrast
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_saturated_background_contrast() {
+        let build = |derivation| {
+            ThemeBuilder::dark()
+                .bg_color(Srgba::new(0.35, 0.1, 0.6, 1.0))
+                .derivation(derivation)
+                .build()
+        };
+
+        let oklch = min_text_contrast(&build(Derivation::Oklch));
+        let tonal = min_text_contrast(&build(Derivation::Tonal));
+        assert!(tonal > oklch, "{} <= {}", tonal, oklch);
+        assert!(tonal >= 4.5, "{}", tonal);
+    }
+
+    #[test]
+    fn test_translucency() {
+        let translucency = Translucency {
+            window: 0.9,
+            background: 1.5,
+            primary: 0.8,
+            secondary: -1.0,
+            blur: true,
+        };
+
+        let theme = ThemeBuilder::dark().translucency(translucency).build();
+        assert_eq!(theme.window_opacity(), 0.9);
+        assert_eq!(theme.layer_opacity(Layer::Background), 1.0);
+        assert_eq!(theme.layer_opacity(Layer::Primary), 0.8);
+        assert_eq!(theme.layer_opacity(Layer::Secondary), 0.0);
+        assert!(theme.translucency().blur);
+
+        let theme = ThemeBuilder::dark_high_contrast()
+            .translucency(translucency)
+            .build();
+        assert_eq!(theme.translucency(), Translucency::OPAQUE);
+
+        // hand edited high contrast themes are still opaque
+        let mut theme = Theme::high_contrast_dark_default();
+        theme.translucency = translucency;
+        assert_eq!(theme.window_opacity(), 1.0);
+    }
+
+    #[test]
+    fn test_active_high_contrast() {
+        let dark = ThemeBuilder::active_dark_high_contrast().build();
+        assert!(dark.is_dark && dark.is_high_contrast);
+        let light = ThemeBuilder::active_light_high_contrast().build();
+        assert!(!light.is_dark && light.is_high_contrast);
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+use crate::Layer;
+
+/// Translucency variables for the Cosmic theme
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Translucency {
+    /// opacity of the window background
+    pub window: f32,
+    /// opacity of background containers
+    pub background: f32,
+    /// opacity of primary containers
+    pub primary: f32,
+    /// opacity of secondary containers
+    pub secondary: f32,
+    /// hint to the compositor to blur content behind translucent surfaces
+    pub blur: bool,
+}
+
+impl Default for Translucency {
+    fn default() -> Self {
+        Self::OPAQUE
+    }
+}
+
+impl Translucency {
+    /// fully opaque surfaces without blur
+    pub const OPAQUE: Self = Self {
+        window: 1.0,
+        background: 1.0,
+        primary: 1.0,
+        secondary: 1.0,
+        blur: false,
+    };
+
+    /// get the opacity of containers on a layer
+    pub fn layer(&self, layer: Layer) -> f32 {
+        match layer {
+            Layer::Background => self.background,
+            Layer::Primary => self.primary,
+            Layer::Secondary => self.secondary,
+        }
+    }
+
+    /// whether any surface is translucent
+    pub fn is_translucent(&self) -> bool {
+        [self.window, self.background, self.primary, self.secondary]
+            .into_iter()
+            .any(|alpha| alpha < 1.0)
+    }
+
+    /// clamp opacities to the valid range
+    /// high contrast themes are always opaque, as translucency reduces the contrast of text
+    pub fn clamped(self, is_high_contrast: bool) -> Self {
+        if is_high_contrast {
+            return Self::OPAQUE;
+        }
+
+        let clamp = |alpha: f32| {
+            if alpha.is_nan() {
+                1.0
+            } else {
+                alpha.clamp(0.0, 1.0)
+            }
+        };
+        Self {
+            window: clamp(self.window),
+            background: clamp(self.background),
+            primary: clamp(self.primary),
+            secondary: clamp(self.secondary),
+            blur: self.blur && self.is_translucent(),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+/// Weight of a font
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum FontWeight {
+    /// thin weight (100)
+    Thin,
+    /// extra light weight (200)
+    ExtraLight,
+    /// light weight (300)
+    Light,
+    /// normal weight (400)
+    #[default]
+    Normal,
+    /// medium weight (500)
+    Medium,
+    /// semibold weight (600)
+    Semibold,
+    /// bold weight (700)
+    Bold,
+    /// extra bold weight (800)
+    ExtraBold,
+    /// black weight (900)
+    Black,
+}
+
+impl FontWeight {
+    /// numeric weight, as used by CSS and OpenType
+    pub fn value(self) -> u16 {
+        match self {
+            Self::Thin => 100,
+            Self::ExtraLight => 200,
+            Self::Light => 300,
+            Self::Normal => 400,
+            Self::Medium => 500,
+            Self::Semibold => 600,
+            Self::Bold => 700,
+            Self::ExtraBold => 800,
+            Self::Black => 900,
+        }
+    }
+}
+
+/// Size and weight of a style of text
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TextStyle {
+    /// size of the text
+    pub size: f32,
+    /// weight of the text
+    pub weight: FontWeight,
+}
+
+impl TextStyle {
+    /// create a new text style
+    pub const fn new(size: f32, weight: FontWeight) -> Self {
+        Self { size, weight }
+    }
+}
+
+/// Typography variables for the Cosmic theme
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Typography {
+    /// font family used for interface text
+    pub family: String,
+    /// font family used for monospaced text
+    pub monospace_family: String,
+    /// text style of titles
+    pub title: TextStyle,
+    /// text style of headings
+    pub heading: TextStyle,
+    /// text style of body text
+    pub body: TextStyle,
+    /// text style of captions
+    pub caption: TextStyle,
+    /// text style of monospaced text
+    pub monospace: TextStyle,
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        Self {
+            family: "Fira Sans".to_string(),
+            monospace_family: "Fira Mono".to_string(),
+            title: TextStyle::new(24.0, FontWeight::Light),
+            heading: TextStyle::new(16.0, FontWeight::Semibold),
+            body: TextStyle::new(14.0, FontWeight::Normal),
+            caption: TextStyle::new(10.0, FontWeight::Normal),
+            monospace: TextStyle::new(14.0, FontWeight::Normal),
+        }
+    }
+}
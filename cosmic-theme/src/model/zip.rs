@@ -0,0 +1,121 @@
+use palette::Srgba;
+
+use crate::{Component, Container, CosmicPaletteInner, Elevation, Shadow, Theme};
+
+/// combine the colors of two themes, keeping every other variable of `b`
+pub(crate) fn zip_theme(
+    a: &Theme<Srgba>,
+    b: &Theme<Srgba>,
+    f: impl Fn(Srgba, Srgba) -> Srgba + Copy,
+) -> Theme<Srgba> {
+    let mut theme = b.clone();
+    theme.background = zip_container(&a.background, &b.background, f);
+    theme.primary = zip_container(&a.primary, &b.primary, f);
+    theme.secondary = zip_container(&a.secondary, &b.secondary, f);
+    theme.accent = zip_component(&a.accent, &b.accent, f);
+    theme.success = zip_component(&a.success, &b.success, f);
+    theme.destructive = zip_component(&a.destructive, &b.destructive, f);
+    theme.warning = zip_component(&a.warning, &b.warning, f);
+    theme.accent_button = zip_component(&a.accent_button, &b.accent_button, f);
+    theme.success_button = zip_component(&a.success_button, &b.success_button, f);
+    theme.destructive_button = zip_component(&a.destructive_button, &b.destructive_button, f);
+    theme.warning_button = zip_component(&a.warning_button, &b.warning_button, f);
+    theme.text_button = zip_component(&a.text_button, &b.text_button, f);
+    theme.button = zip_component(&a.button, &b.button, f);
+    theme.palette = zip_palette(&a.palette, &b.palette, f);
+    theme.elevation = zip_elevation(&a.elevation, &b.elevation, f);
+    theme
+}
+
+fn zip_container(
+    a: &Container<Srgba>,
+    b: &Container<Srgba>,
+    f: impl Fn(Srgba, Srgba) -> Srgba + Copy,
+) -> Container<Srgba> {
+    Container {
+        base: f(a.base, b.base),
+        component: zip_component(&a.component, &b.component, f),
+        divider: f(a.divider, b.divider),
+        on: f(a.on, b.on),
+    }
+}
+
+fn zip_component(
+    a: &Component<Srgba>,
+    b: &Component<Srgba>,
+    f: impl Fn(Srgba, Srgba) -> Srgba,
+) -> Component<Srgba> {
+    Component {
+        base: f(a.base, b.base),
+        hover: f(a.hover, b.hover),
+        pressed: f(a.pressed, b.pressed),
+        selected: f(a.selected, b.selected),
+        selected_text: f(a.selected_text, b.selected_text),
+        focus: f(a.focus, b.focus),
+        divider: f(a.divider, b.divider),
+        on: f(a.on, b.on),
+        disabled: f(a.disabled, b.disabled),
+        on_disabled: f(a.on_disabled, b.on_disabled),
+        border: f(a.border, b.border),
+        disabled_border: f(a.disabled_border, b.disabled_border),
+    }
+}
+
+fn zip_elevation(
+    a: &Elevation<Srgba>,
+    b: &Elevation<Srgba>,
+    f: impl Fn(Srgba, Srgba) -> Srgba + Copy,
+) -> Elevation<Srgba> {
+    let shadow = |a: &Shadow<Srgba>, b: &Shadow<Srgba>| Shadow {
+        color: f(a.color, b.color),
+        border: b.border.map(|border| f(a.border.unwrap_or(border), border)),
+        ..b.clone()
+    };
+    Elevation {
+        flat: shadow(&a.flat, &b.flat),
+        raised: shadow(&a.raised, &b.raised),
+        overlay: shadow(&a.overlay, &b.overlay),
+        modal: shadow(&a.modal, &b.modal),
+    }
+}
+
+fn zip_palette(
+    a: &CosmicPaletteInner<Srgba>,
+    b: &CosmicPaletteInner<Srgba>,
+    f: impl Fn(Srgba, Srgba) -> Srgba,
+) -> CosmicPaletteInner<Srgba> {
+    CosmicPaletteInner {
+        name: b.name.clone(),
+        blue: f(a.blue, b.blue),
+        red: f(a.red, b.red),
+        green: f(a.green, b.green),
+        yellow: f(a.yellow, b.yellow),
+        gray_1: f(a.gray_1, b.gray_1),
+        gray_2: f(a.gray_2, b.gray_2),
+        gray_3: f(a.gray_3, b.gray_3),
+        neutral_0: f(a.neutral_0, b.neutral_0),
+        neutral_1: f(a.neutral_1, b.neutral_1),
+        neutral_2: f(a.neutral_2, b.neutral_2),
+        neutral_3: f(a.neutral_3, b.neutral_3),
+        neutral_4: f(a.neutral_4, b.neutral_4),
+        neutral_5: f(a.neutral_5, b.neutral_5),
+        neutral_6: f(a.neutral_6, b.neutral_6),
+        neutral_7: f(a.neutral_7, b.neutral_7),
+        neutral_8: f(a.neutral_8, b.neutral_8),
+        neutral_9: f(a.neutral_9, b.neutral_9),
+        neutral_10: f(a.neutral_10, b.neutral_10),
+        ext_warm_grey: f(a.ext_warm_grey, b.ext_warm_grey),
+        ext_orange: f(a.ext_orange, b.ext_orange),
+        ext_yellow: f(a.ext_yellow, b.ext_yellow),
+        ext_blue: f(a.ext_blue, b.ext_blue),
+        ext_purple: f(a.ext_purple, b.ext_purple),
+        ext_pink: f(a.ext_pink, b.ext_pink),
+        ext_indigo: f(a.ext_indigo, b.ext_indigo),
+        accent_warm_grey: f(a.accent_warm_grey, b.accent_warm_grey),
+        accent_orange: f(a.accent_orange, b.accent_orange),
+        accent_yellow: f(a.accent_yellow, b.accent_yellow),
+        accent_purple: f(a.accent_purple, b.accent_purple),
+        accent_pink: f(a.accent_pink, b.accent_pink),
+        accent_indigo: f(a.accent_indigo, b.accent_indigo),
+    }
+}
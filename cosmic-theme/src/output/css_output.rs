@@ -0,0 +1,390 @@
+use crate::{
+    Component, Container, CornerRadii, CosmicPaletteInner, Density, Elevation, Layout, Spacing,
+    TextStyle, Theme, Translucency, Typography,
+};
+use palette::Srgba;
+use std::fmt::Write;
+
+/// prefix of every CSS custom property emitted for a theme
+pub const CSS_PREFIX: &str = "--cosmic";
+
+/// Trait for outputting the Theme variables as CSS custom properties
+///
+/// Properties are named `--cosmic-{group}-{token}`, with words separated by dashes.
+/// For example, `--cosmic-background-component-hover`, `--cosmic-accent-button-base`,
+/// `--cosmic-palette-neutral-5`, `--cosmic-space-xs`, `--cosmic-radius-m`,
+/// `--cosmic-text-body-size`, `--cosmic-shadow-overlay`, `--cosmic-opacity-primary` and
+/// `--cosmic-button-height`.
+pub trait CssOutput {
+    /// get the name and value of every custom property of the theme
+    fn css_variables(&self) -> Vec<(String, String)>;
+
+    /// get a CSS rule declaring the custom properties of the theme for a selector
+    fn as_css_rule(&self, selector: &str) -> String;
+}
+
+impl CssOutput for Theme<Srgba> {
+    fn css_variables(&self) -> Vec<(String, String)> {
+        let mut vars = Vec::new();
+
+        container_vars(&mut vars, "background", &self.background);
+        container_vars(&mut vars, "primary", &self.primary);
+        container_vars(&mut vars, "secondary", &self.secondary);
+
+        for (name, component) in [
+            ("accent", &self.accent),
+            ("success", &self.success),
+            ("destructive", &self.destructive),
+            ("warning", &self.warning),
+            ("accent-button", &self.accent_button),
+            ("success-button", &self.success_button),
+            ("destructive-button", &self.destructive_button),
+            ("warning-button", &self.warning_button),
+            ("text-button", &self.text_button),
+            ("button", &self.button),
+        ] {
+            component_vars(&mut vars, name, component);
+        }
+
+        palette_vars(&mut vars, &self.palette);
+        spacing_vars(&mut vars, &self.spacing);
+        radii_vars(&mut vars, &self.corner_radii);
+        layout_vars(&mut vars, &self.layout);
+        typography_vars(&mut vars, &self.typography);
+        elevation_vars(&mut vars, &self.elevation);
+        translucency_vars(&mut vars, &self.translucency());
+
+        vars
+    }
+
+    fn as_css_rule(&self, selector: &str) -> String {
+        let mut css = format!("{selector} {{\n");
+        let scheme = if self.is_dark { "dark" } else { "light" };
+        let _ = writeln!(css, "  color-scheme: {scheme};");
+        for (name, value) in self.css_variables() {
+            let _ = writeln!(css, "  {name}: {value};");
+        }
+        css.push_str("}\n");
+        css
+    }
+}
+
+/// get a stylesheet which declares the custom properties of the light theme on `:root`,
+/// and those of the dark theme when the user prefers a dark color scheme
+pub fn css_stylesheet(light: &Theme<Srgba>, dark: &Theme<Srgba>) -> String {
+    let mut css = light.as_css_rule(":root");
+    css.push_str("\n@media (prefers-color-scheme: dark) {\n");
+    for line in dark.as_css_rule(":root").lines() {
+        let _ = writeln!(css, "  {line}");
+    }
+    css.push_str("}\n");
+    css
+}
+
+/// format a color as a CSS hex color, including its alpha
+pub fn css_color(color: Srgba) -> String {
+    let Srgba {
+        color: rgb, alpha, ..
+    } = color.into_format::<u8, u8>();
+    format!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        rgb.red, rgb.green, rgb.blue, alpha
+    )
+}
+
+/// format a value as a quoted CSS string
+fn css_string(value: &str) -> String {
+    let mut css = String::with_capacity(value.len() + 2);
+    css.push('"');
+    for c in value.chars() {
+        match c {
+            '"' | '\\' => {
+                css.push('\\');
+                css.push(c);
+            }
+            // an escaped newline continues the line, so newlines are written as their code point
+            '\n' => css.push_str("\\a "),
+            c => css.push(c),
+        }
+    }
+    css.push('"');
+    css
+}
+
+fn var(vars: &mut Vec<(String, String)>, name: &str, value: String) {
+    vars.push((format!("{CSS_PREFIX}-{name}"), value));
+}
+
+fn container_vars(vars: &mut Vec<(String, String)>, name: &str, container: &Container<Srgba>) {
+    var(vars, &format!("{name}-base"), css_color(container.base));
+    var(
+        vars,
+        &format!("{name}-divider"),
+        css_color(container.divider),
+    );
+    var(vars, &format!("{name}-on"), css_color(container.on));
+    component_vars(vars, &format!("{name}-component"), &container.component);
+}
+
+fn component_vars(vars: &mut Vec<(String, String)>, name: &str, component: &Component<Srgba>) {
+    for (state, color) in [
+        ("base", component.base),
+        ("hover", component.hover),
+        ("pressed", component.pressed),
+        ("selected", component.selected),
+        ("selected-text", component.selected_text),
+        ("focus", component.focus),
+        ("divider", component.divider),
+        ("on", component.on),
+        ("disabled", component.disabled),
+        ("on-disabled", component.on_disabled),
+        ("border", component.border),
+        ("disabled-border", component.disabled_border),
+    ] {
+        var(vars, &format!("{name}-{state}"), css_color(color));
+    }
+}
+
+fn palette_vars(vars: &mut Vec<(String, String)>, palette: &CosmicPaletteInner<Srgba>) {
+    for (name, color) in [
+        ("blue", palette.blue),
+        ("red", palette.red),
+        ("green", palette.green),
+        ("yellow", palette.yellow),
+        ("gray-1", palette.gray_1),
+        ("gray-2", palette.gray_2),
+        ("gray-3", palette.gray_3),
+        ("neutral-0", palette.neutral_0),
+        ("neutral-1", palette.neutral_1),
+        ("neutral-2", palette.neutral_2),
+        ("neutral-3", palette.neutral_3),
+        ("neutral-4", palette.neutral_4),
+        ("neutral-5", palette.neutral_5),
+        ("neutral-6", palette.neutral_6),
+        ("neutral-7", palette.neutral_7),
+        ("neutral-8", palette.neutral_8),
+        ("neutral-9", palette.neutral_9),
+        ("neutral-10", palette.neutral_10),
+        ("ext-warm-grey", palette.ext_warm_grey),
+        ("ext-orange", palette.ext_orange),
+        ("ext-yellow", palette.ext_yellow),
+        ("ext-blue", palette.ext_blue),
+        ("ext-purple", palette.ext_purple),
+        ("ext-pink", palette.ext_pink),
+        ("ext-indigo", palette.ext_indigo),
+        ("accent-warm-grey", palette.accent_warm_grey),
+        ("accent-orange", palette.accent_orange),
+        ("accent-yellow", palette.accent_yellow),
+        ("accent-purple", palette.accent_purple),
+        ("accent-pink", palette.accent_pink),
+        ("accent-indigo", palette.accent_indigo),
+    ] {
+        var(vars, &format!("palette-{name}"), css_color(color));
+    }
+}
+
+fn spacing_vars(vars: &mut Vec<(String, String)>, spacing: &Spacing) {
+    for (name, space) in [
+        ("none", spacing.space_none),
+        ("xxxs", spacing.space_xxxs),
+        ("xxs", spacing.space_xxs),
+        ("xs", spacing.space_xs),
+        ("s", spacing.space_s),
+        ("m", spacing.space_m),
+        ("l", spacing.space_l),
+        ("xl", spacing.space_xl),
+        ("xxl", spacing.space_xxl),
+        ("xxxl", spacing.space_xxxl),
+    ] {
+        var(vars, &format!("space-{name}"), format!("{space}px"));
+    }
+}
+
+fn radii_vars(vars: &mut Vec<(String, String)>, radii: &CornerRadii) {
+    for (name, radius) in [
+        ("0", radii.radius_0),
+        ("xs", radii.radius_xs),
+        ("s", radii.radius_s),
+        ("m", radii.radius_m),
+        ("l", radii.radius_l),
+        ("xl", radii.radius_xl),
+    ] {
+        // corners are ordered top left, top right, bottom right, bottom left, as in CSS
+        let [top_left, top_right, bottom_right, bottom_left] = radius;
+        var(
+            vars,
+            &format!("radius-{name}"),
+            format!("{top_left}px {top_right}px {bottom_right}px {bottom_left}px"),
+        );
+    }
+}
+
+fn layout_vars(vars: &mut Vec<(String, String)>, layout: &Layout) {
+    let density = match layout.density {
+        Density::Compact => "compact",
+        Density::Comfortable => "comfortable",
+        Density::Spacious => "spacious",
+    };
+    var(vars, "density", density.to_string());
+
+    for (name, size) in [
+        ("button-height", layout.button_height),
+        ("segmented-button-height", layout.segmented_button_height),
+        ("view-switcher-height", layout.view_switcher_height),
+        ("header-bar-height", layout.header_bar_height),
+        ("list-padding", layout.list_padding),
+    ] {
+        var(vars, name, format!("{size}px"));
+    }
+}
+
+fn typography_vars(vars: &mut Vec<(String, String)>, typography: &Typography) {
+    var(vars, "font-family", css_string(&typography.family));
+    var(
+        vars,
+        "font-family-monospace",
+        css_string(&typography.monospace_family),
+    );
+
+    let styles: [(&str, &TextStyle); 5] = [
+        ("title", &typography.title),
+        ("heading", &typography.heading),
+        ("body", &typography.body),
+        ("caption", &typography.caption),
+        ("monospace", &typography.monospace),
+    ];
+    for (name, style) in styles {
+        var(
+            vars,
+            &format!("text-{name}-size"),
+            format!("{}px", style.size),
+        );
+        var(
+            vars,
+            &format!("text-{name}-weight"),
+            style.weight.value().to_string(),
+        );
+    }
+}
+
+fn elevation_vars(vars: &mut Vec<(String, String)>, elevation: &Elevation<Srgba>) {
+    for (name, shadow) in [
+        ("flat", &elevation.flat),
+        ("raised", &elevation.raised),
+        ("overlay", &elevation.overlay),
+        ("modal", &elevation.modal),
+    ] {
+        let box_shadow = if shadow.color.alpha > 0.0 {
+            let [x, y] = shadow.offset;
+            format!(
+                "{x}px {y}px {}px {}",
+                shadow.blur_radius,
+                css_color(shadow.color)
+            )
+        } else {
+            "none".to_string()
+        };
+        var(vars, &format!("shadow-{name}"), box_shadow);
+
+        let outline = match shadow.border {
+            Some(color) if shadow.border_width > 0.0 => {
+                format!("{}px solid {}", shadow.border_width, css_color(color))
+            }
+            _ => "none".to_string(),
+        };
+        var(vars, &format!("outline-{name}"), outline);
+    }
+}
+
+fn translucency_vars(vars: &mut Vec<(String, String)>, translucency: &Translucency) {
+    for (name, opacity) in [
+        ("window", translucency.window),
+        ("background", translucency.background),
+        ("primary", translucency.primary),
+        ("secondary", translucency.secondary),
+    ] {
+        var(vars, &format!("opacity-{name}"), opacity.to_string());
+    }
+    let blur = if translucency.blur { "1" } else { "0" };
+    var(vars, "backdrop-blur", blur.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{css_color, css_string, css_stylesheet, CssOutput};
+    use crate::{Theme, Translucency};
+    use palette::Srgba;
+
+    #[test]
+    fn test_css_color() {
+        assert_eq!(css_color(Srgba::new(1.0, 0.0, 0.5, 1.0)), "#ff0080ff");
+        assert_eq!(css_color(Srgba::new(0.0, 0.0, 0.0, 0.0)), "#00000000");
+    }
+
+    #[test]
+    fn test_css_string() {
+        assert_eq!(css_string("Fira Sans"), "\"Fira Sans\"");
+        assert_eq!(css_string("Noto Sans CJK 日本"), "\"Noto Sans CJK 日本\"");
+        assert_eq!(css_string(r#"a "b" \c"#), r#""a \"b\" \\c""#);
+        assert_eq!(css_string("a\nb"), "\"a\\a b\"");
+    }
+
+    #[test]
+    fn test_css_high_contrast_translucency() {
+        let mut theme = Theme::high_contrast_dark_default();
+        theme.translucency = Translucency {
+            primary: 0.5,
+            ..Translucency::default()
+        };
+        let vars = theme.css_variables();
+        let opacity = vars
+            .iter()
+            .find(|(n, _)| n == "--cosmic-opacity-primary")
+            .map(|(_, v)| v.as_str());
+        assert_eq!(opacity, Some("1"));
+    }
+
+    #[test]
+    fn test_css_variables() {
+        let theme = Theme::dark_default();
+        let vars = theme.css_variables();
+
+        let get = |name: &str| {
+            vars.iter()
+                .find(|(n, _)| n == name)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(
+            get("--cosmic-background-base"),
+            Some(css_color(theme.background.base).as_str())
+        );
+        assert!(get("--cosmic-primary-component-hover").is_some());
+        assert!(get("--cosmic-accent-button-selected-text").is_some());
+        assert!(get("--cosmic-palette-neutral-10").is_some());
+        assert_eq!(get("--cosmic-space-xxs"), Some("8px"));
+        assert!(get("--cosmic-radius-m").unwrap().ends_with("px"));
+        assert_eq!(get("--cosmic-text-body-size"), Some("14px"));
+        assert_eq!(get("--cosmic-text-heading-weight"), Some("600"));
+        assert_eq!(get("--cosmic-font-family"), Some("\"Fira Sans\""));
+        assert_eq!(get("--cosmic-button-height"), Some("32px"));
+        assert_eq!(get("--cosmic-density"), Some("comfortable"));
+        assert_eq!(get("--cosmic-opacity-primary"), Some("1"));
+        assert!(get("--cosmic-shadow-overlay").is_some());
+        assert!(get("--cosmic-outline-modal").is_some());
+
+        // names are unique
+        let mut names: Vec<_> = vars.iter().map(|(n, _)| n).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), vars.len());
+    }
+
+    #[test]
+    fn test_css_stylesheet() {
+        let css = css_stylesheet(&Theme::light_default(), &Theme::dark_default());
+        let media = css.find("@media (prefers-color-scheme: dark)").unwrap();
+        assert!(css[..media].contains("color-scheme: light;"));
+        assert!(css[media..].contains("color-scheme: dark;"));
+        assert_eq!(css.matches('{').count(), css.matches('}').count());
+    }
+}
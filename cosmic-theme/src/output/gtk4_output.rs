@@ -1,6 +1,6 @@
 use crate::{
     model::{Accent, Container, ContainerType, Destructive, Widget},
-    Hex, Theme, NAME,
+    Hex, Theme, NAME, THEME_DIR,
 };
 use anyhow::{bail, Result};
 use palette::Srgba;
@@ -8,7 +8,6 @@ use serde::{de::DeserializeOwned, Serialize};
 use std::{fmt, fs::File, io::prelude::*, path::PathBuf};
 
 pub(crate) const CSS_DIR: &'static str = "css";
-pub(crate) const THEME_DIR: &'static str = "themes";
 
 /// Trait for outputting the Theme variables as Gtk4CSS
 pub trait Gtk4Output {
@@ -6,3 +6,7 @@ pub use gtk4_output::*;
 
 #[cfg(feature = "ron-serialization")]
 pub use ron::*;
+
+/// Module for outputting the Cosmic theme as CSS custom properties
+pub mod css_output;
+pub use css_output::*;
@@ -1,9 +1,53 @@
-use std::num::NonZeroUsize;
+use std::{f32::consts::PI, num::NonZeroUsize};
 
 use almost::equal;
-use palette::{convert::FromColorUnclamped, ClampAssign, FromColor, Oklcha, Srgb, Srgba};
+use palette::{
+    cam16::{BakedParameters, Cam16Jch, Parameters, StaticWp},
+    convert::FromColorUnclamped,
+    hues::Cam16Hue,
+    white_point::D65,
+    ClampAssign, FromColor, Lab, LinSrgb, Oklcha, Srgb, Srgba, Xyz,
+};
+use serde::{Deserialize, Serialize};
 
-/// Get an array of 100 colors with a specific hue and chroma
+/// Maximum chroma of the tuned Oklch ramp, which keeps tints of saturated colors clean
+pub const MAX_TUNED_CHROMA: f32 = 0.1;
+
+/// Adapting luminance of the viewing conditions used for HCT
+const HCT_ADAPTING_LUMINANCE: f32 = 11.72;
+
+/// Strategy for deriving a ramp of colors over the full range of lightness
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum Derivation {
+    /// lightness ramp in Oklch, keeping the hue and chroma of the color
+    #[default]
+    Oklch,
+    /// lightness ramp in Oklch, with chroma limited and reduced towards black and white
+    TunedOklch,
+    /// tone palette in HCT, keeping the CAM16 hue and chroma of the color over the CIE L* tones
+    Tonal,
+}
+
+impl Derivation {
+    /// get a ramp of colors over the full range of lightness
+    pub fn steps(self, c: Srgba, len: NonZeroUsize) -> Vec<Srgba> {
+        match self {
+            Self::Oklch => steps(c, len),
+            Self::TunedOklch => tuned_steps(c, len),
+            Self::Tonal => tonal_steps(c, len),
+        }
+    }
+
+    /// get the index into a ramp of colors for a given color
+    pub fn index(self, c: Srgba, len: usize) -> usize {
+        match self {
+            Self::Oklch | Self::TunedOklch => color_index(c, len),
+            Self::Tonal => tone_index(c, len),
+        }
+    }
+}
+
+/// Get an array of colors with a specific hue and chroma
 /// over the full range of lightness.
 /// Colors which are not valid Srgba will fallback to a color with the nearest valid chroma.
 pub fn steps<C>(c: C, len: NonZeroUsize) -> Vec<Srgba>
@@ -14,13 +58,119 @@ where
     let mut steps = Vec::with_capacity(len.get());
 
     for i in 0..len.get() {
-        let lightness = i as f32 / (len.get() - 1) as f32;
-        c.l = lightness;
+        c.l = ramp_position(i, len);
         steps.push(oklch_to_srgba_nearest_chroma(c))
     }
     steps
 }
 
+/// Get an array of colors with a specific hue over the full range of lightness.
+/// The chroma is limited to [`MAX_TUNED_CHROMA`] and reduced towards black and white.
+pub fn tuned_steps<C>(c: C, len: NonZeroUsize) -> Vec<Srgba>
+where
+    Oklcha: FromColor<C>,
+{
+    let mut c = Oklcha::from_color(c);
+    let chroma = c.chroma.min(MAX_TUNED_CHROMA);
+    let mut steps = Vec::with_capacity(len.get());
+
+    for i in 0..len.get() {
+        c.l = ramp_position(i, len);
+        c.chroma = chroma * (PI * c.l).sin();
+        steps.push(oklch_to_srgba_nearest_chroma(c))
+    }
+    steps
+}
+
+/// Get an array of colors with the HCT hue and chroma of a color over the full range of tones.
+/// Colors which are not valid Srgba will fallback to the nearest valid chroma.
+pub fn tonal_steps(c: Srgba, len: NonZeroUsize) -> Vec<Srgba> {
+    let parameters = hct_parameters();
+    let xyz = Xyz::<D65, f32>::from_color(c.color);
+    let cam = Cam16Jch::from_xyz(xyz, parameters);
+    let mut steps = Vec::with_capacity(len.get());
+
+    for i in 0..len.get() {
+        let tone = ramp_position(i, len) * 100.0;
+        let mut step = Srgba::from(hct_to_srgb(cam.hue, cam.chroma, tone, parameters));
+        step.alpha = c.alpha;
+        steps.push(step);
+    }
+    steps
+}
+
+/// get the index into a tone palette for a given color
+/// the index is the CIE L* tone of the color, scaled to the positions of the palette
+pub fn tone_index(c: Srgba, array_len: usize) -> usize {
+    let last = array_len.saturating_sub(1);
+    let tone = Lab::<D65, f32>::from_color(c.color).l / 100.0;
+    ((tone * last as f32).round() as usize).min(last)
+}
+
+fn ramp_position(i: usize, len: NonZeroUsize) -> f32 {
+    if len.get() == 1 {
+        return 0.5;
+    }
+    i as f32 / (len.get() - 1) as f32
+}
+
+fn hct_parameters() -> BakedParameters<StaticWp<D65>, f32> {
+    Parameters::<StaticWp<D65>, f32>::default_static_wp(HCT_ADAPTING_LUMINANCE).bake()
+}
+
+/// find the color with a CAM16 hue and chroma at a CIE L* tone
+/// chroma is reduced until the color is valid srgb
+fn hct_to_srgb(
+    hue: Cam16Hue<f32>,
+    chroma: f32,
+    tone: f32,
+    parameters: BakedParameters<StaticWp<D65>, f32>,
+) -> Srgb {
+    if tone <= 0.0 {
+        return Srgb::new(0.0, 0.0, 0.0);
+    } else if tone >= 100.0 {
+        return Srgb::new(1.0, 1.0, 1.0);
+    }
+
+    let y = Xyz::<D65, f32>::from_color(Lab::<D65, f32>::new(tone, 0.0, 0.0)).y;
+
+    // the luminance of a CAM16 color grows with its lightness, so find the lightness with the tone
+    let solve = |chroma: f32| {
+        let (mut low, mut high) = (0.0, 100.0);
+        let mut xyz = Xyz::default();
+        for _ in 0..24 {
+            let j = (low + high) / 2.0;
+            xyz = Cam16Jch::new(j, chroma, hue).into_xyz(parameters);
+            if xyz.y < y {
+                low = j;
+            } else {
+                high = j;
+            }
+        }
+        Srgb::from_linear(LinSrgb::from_color_unclamped(xyz))
+    };
+
+    let c = solve(chroma);
+    if is_valid_srgb(c.into()) {
+        return c;
+    }
+
+    let (mut low, mut high) = (0.0, chroma);
+    let mut c = solve(low);
+    for _ in 0..16 {
+        let mid = (low + high) / 2.0;
+        let mid_c = solve(mid);
+        if is_valid_srgb(mid_c.into()) {
+            low = mid;
+            c = mid_c;
+        } else {
+            high = mid;
+        }
+    }
+    c.clamp_assign();
+    c
+}
+
 /// get the index for a new color some steps away from a base color
 pub fn get_index(base_index: usize, steps: usize, step_len: usize, is_dark: bool) -> Option<usize> {
     if is_dark {
@@ -31,19 +181,25 @@ pub fn get_index(base_index: usize, steps: usize, step_len: usize, is_dark: bool
     .filter(|i| *i < step_len)
 }
 
+/// scale a number of steps in a ramp of 100 colors to a ramp of another length
+pub fn scale_steps(steps: usize, step_len: usize) -> usize {
+    (steps as f32 * step_len.saturating_sub(1) as f32 / 99.0).round() as usize
+}
+
 /// get surface color given a base and some steps
+/// steps are relative to a ramp of 100 colors, and scaled to the length of the ramp
 pub fn get_surface_color(
     base_index: usize,
     steps: usize,
-    step_array: &Vec<Srgba>,
+    step_array: &[Srgba],
     mut is_dark: bool,
     fallback: &Srgba,
 ) -> Srgba {
-    assert!(step_array.len() == 100);
-    if !is_dark && base_index >= 88 {
+    let len = step_array.len();
+    if !is_dark && base_index >= scale_steps(88, len) {
         is_dark = true;
     }
-    get_index(base_index, steps, step_array.len(), is_dark)
+    get_index(base_index, scale_steps(steps, len), len, is_dark)
         .and_then(|i| step_array.get(i).cloned())
         .unwrap_or_else(|| fallback.to_owned())
 }
@@ -51,25 +207,33 @@ pub fn get_surface_color(
 /// get text color given a base background color
 pub fn get_text(
     base_index: usize,
-    step_array: &Vec<Srgba>,
+    step_array: &[Srgba],
     is_dark: bool,
     fallback: &Srgba,
-    tint_array: Option<&Vec<Srgba>>,
+    tint_array: Option<&[Srgba]>,
 ) -> Srgba {
-    assert!(step_array.len() == 100);
-    let step_array = if let Some(tint_array) = tint_array {
-        assert!(tint_array.len() == 100);
-        tint_array
-    } else {
-        step_array
-    };
-    let Some(index) = get_index(base_index, 70, step_array.len(), is_dark)
-        .or_else(|| get_index(base_index, 50, step_array.len(), is_dark))
+    let len = step_array.len();
+    let Some(index) = get_index(base_index, scale_steps(70, len), len, is_dark)
+        .or_else(|| get_index(base_index, scale_steps(50, len), len, is_dark))
     else {
         return fallback.to_owned();
     };
 
-    step_array
+    let Some(tint_array) = tint_array else {
+        return step_array
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| fallback.to_owned());
+    };
+
+    // the tint ramp may have a different length
+    let index = if len > 1 {
+        (index as f32 * tint_array.len().saturating_sub(1) as f32 / (len - 1) as f32).round()
+            as usize
+    } else {
+        index
+    };
+    tint_array
         .get(index)
         .cloned()
         .unwrap_or_else(|| fallback.to_owned())
@@ -122,10 +286,14 @@ pub fn is_valid_srgb(c: Srgba) -> bool {
 
 #[cfg(test)]
 mod tests {
+    use std::num::NonZeroUsize;
+
     use almost::equal;
     use palette::{OklabHue, Srgba};
 
-    use super::{is_valid_srgb, oklch_to_srgba_nearest_chroma};
+    use super::{
+        is_valid_srgb, oklch_to_srgba_nearest_chroma, tonal_steps, tone_index, Derivation,
+    };
 
     #[test]
     fn test_valid_check() {
@@ -203,4 +371,35 @@ mod tests {
         assert!(srgb.green == 19);
         assert!(srgb.blue == 0);
     }
+
+    #[test]
+    fn test_derivation_ramps() {
+        let c = Srgba::new(0.35, 0.1, 0.6, 1.0);
+        for derivation in [Derivation::Oklch, Derivation::TunedOklch, Derivation::Tonal] {
+            for len in [2, 11, 100] {
+                let steps = derivation.steps(c, NonZeroUsize::new(len).unwrap());
+                assert_eq!(steps.len(), len);
+
+                let (first, last) = (steps[0], steps[len - 1]);
+                assert!(first.red < 0.01 && first.green < 0.01 && first.blue < 0.01);
+                assert!(last.red > 0.99 && last.green > 0.99 && last.blue > 0.99);
+                assert!(steps.iter().all(|s| is_valid_srgb(*s)));
+
+                let index = derivation.index(c, len);
+                assert!(index < len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tone_index() {
+        let c = Srgba::new(0.35, 0.1, 0.6, 1.0);
+        let len = NonZeroUsize::new(11).unwrap();
+        // every step of a tone palette is found at its own index
+        for (i, step) in tonal_steps(c, len).into_iter().enumerate() {
+            assert_eq!(tone_index(step, len.get()), i);
+        }
+        assert_eq!(tone_index(Srgba::new(1.0, 1.0, 1.0, 1.0), 11), 10);
+        assert_eq!(tone_index(Srgba::new(1.0, 1.0, 1.0, 1.0), 1), 0);
+    }
 }
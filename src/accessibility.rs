@@ -0,0 +1,67 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Accessibility settings shared by every COSMIC application.
+
+use cosmic_config::{
+    config_subscription, cosmic_config_derive::CosmicConfigEntry, Config, ConfigGet, ConfigSet,
+    CosmicConfigEntry,
+};
+use iced_futures::Subscription;
+use serde::{Deserialize, Serialize};
+
+/// ID of the accessibility config.
+pub const ID: &str = "com.system76.CosmicAccessibility";
+
+/// Accessibility settings of the system.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize, CosmicConfigEntry)]
+pub struct Accessibility {
+    /// Disables animations, such as cross-fades between themes.
+    pub reduce_motion: bool,
+}
+
+impl Accessibility {
+    /// Version of the accessibility config.
+    #[must_use]
+    pub fn version() -> u64 {
+        1
+    }
+
+    /// Get the accessibility config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory could not be created.
+    pub fn config() -> Result<Config, cosmic_config::Error> {
+        Config::new(ID, Self::version())
+    }
+}
+
+/// Get the configured [`Accessibility`] settings.
+#[must_use]
+pub fn accessibility() -> Accessibility {
+    let Ok(config) = Accessibility::config() else {
+        return Accessibility::default();
+    };
+
+    Accessibility::get_entry(&config).unwrap_or_else(|(errors, accessibility)| {
+        for err in errors {
+            tracing::error!("{:?}", err);
+        }
+        accessibility
+    })
+}
+
+/// Observe changes to the configured [`Accessibility`] settings.
+pub fn subscription(id: u64) -> Subscription<Accessibility> {
+    config_subscription::<u64, Accessibility>(id, ID.into(), Accessibility::version()).map(
+        |(_, res)| {
+            res.unwrap_or_else(|(errors, accessibility)| {
+                for err in errors {
+                    tracing::error!("{:?}", err);
+                }
+                accessibility
+            })
+        },
+    )
+}
@@ -11,7 +11,7 @@ use crate::{
         window, Color, Length, Limits, Rectangle,
     },
     iced_style, iced_widget, sctk,
-    theme::{self, Button, THEME},
+    theme::{self, elevation::ElevationLevel, Button, THEME},
     Application, Element, Renderer,
 };
 pub use cosmic_panel_config;
@@ -104,7 +104,10 @@ impl CosmicAppletHelper {
         let (width, height) = self.suggested_size();
         let width = u32::from(width);
         let height = u32::from(height);
-        let mut settings = super::Settings::default()
+        let theme = self.theme().unwrap_or_else(crate::theme::theme);
+        let typography = &theme.cosmic().typography;
+        let default_font = crate::font::from_text_style(typography, &typography.body);
+        super::Settings::default()
             .size((width + APPLET_PADDING * 2, height + APPLET_PADDING * 2))
             .size_limits(
                 Limits::NONE
@@ -114,13 +117,11 @@ impl CosmicAppletHelper {
                     .max_width(width as f32 + APPLET_PADDING as f32 * 2.0),
             )
             .resizable(None)
+            // panel applets use larger text than the body style of windows
             .default_text_size(18.0)
-            .default_font(crate::font::FONT)
-            .transparent(true);
-        if let Some(theme) = self.theme() {
-            settings = settings.theme(theme);
-        }
-        settings
+            .default_font(default_font)
+            .transparent(true)
+            .theme(theme)
     }
 
     #[must_use]
@@ -145,15 +146,17 @@ impl CosmicAppletHelper {
             PanelAnchor::Bottom => (Vertical::Bottom, Horizontal::Center),
         };
 
-        Container::<Message, Renderer>::new(Container::<Message, Renderer>::new(content).style(
-            theme::Container::custom(|theme| Appearance {
-                text_color: Some(theme.cosmic().background.on.into()),
-                background: Some(Color::from(theme.cosmic().background.base).into()),
-                border_radius: 12.0.into(),
-                border_width: 0.0,
-                border_color: Color::TRANSPARENT,
-            }),
-        ))
+        Container::<Message, Renderer>::new(
+            crate::widget::container(content)
+                .style(theme::Container::custom(|theme| Appearance {
+                    text_color: Some(theme.cosmic().background.on.into()),
+                    background: Some(Color::from(theme.cosmic().background.base).into()),
+                    border_radius: theme.cosmic().corner_radii.radius_m.into(),
+                    border_width: 0.0,
+                    border_color: Color::TRANSPARENT,
+                }))
+                .elevation(ElevationLevel::Overlay),
+        )
         .width(Length::Shrink)
         .height(Length::Shrink)
         .align_x(horizontal_align)
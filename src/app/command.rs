@@ -3,6 +3,7 @@
 
 /// Asynchronous actions for COSMIC applications.
 use super::Message;
+use iced::window;
 
 /// Commands for COSMIC applications.
 pub type Command<M> = iced::Command<Message<M>>;
@@ -50,3 +51,39 @@ pub fn set_windowed<M: Send + 'static>() -> iced::Command<Message<M>> {
 pub fn toggle_fullscreen<M: Send + 'static>() -> iced::Command<Message<M>> {
     crate::command::toggle_fullscreen().map(Message::Cosmic)
 }
+
+#[cfg(feature = "wayland")]
+pub fn window_close<M: Send + 'static>(id: window::Id) -> iced::Command<Message<M>> {
+    crate::command::window_close(id).map(Message::Cosmic)
+}
+
+pub fn window_drag<M: Send + 'static>(id: window::Id) -> iced::Command<Message<M>> {
+    crate::command::window_drag(id).map(Message::Cosmic)
+}
+
+pub fn window_fullscreen<M: Send + 'static>(id: window::Id) -> iced::Command<Message<M>> {
+    crate::command::window_fullscreen(id).map(Message::Cosmic)
+}
+
+pub fn window_maximize<M: Send + 'static>(id: window::Id) -> iced::Command<Message<M>> {
+    crate::command::window_maximize(id).map(Message::Cosmic)
+}
+
+pub fn window_minimize<M: Send + 'static>(id: window::Id) -> iced::Command<Message<M>> {
+    crate::command::window_minimize(id).map(Message::Cosmic)
+}
+
+pub fn window_set_title<M: Send + 'static>(
+    id: window::Id,
+    title: String,
+) -> iced::Command<Message<M>> {
+    crate::command::window_set_title(id, title).map(Message::Cosmic)
+}
+
+pub fn window_set_windowed<M: Send + 'static>(id: window::Id) -> iced::Command<Message<M>> {
+    crate::command::window_set_windowed(id).map(Message::Cosmic)
+}
+
+pub fn window_toggle_fullscreen<M: Send + 'static>(id: window::Id) -> iced::Command<Message<M>> {
+    crate::command::window_toggle_fullscreen(id).map(Message::Cosmic)
+}
@@ -1,7 +1,19 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::{theme, Theme};
+use std::collections::HashMap;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+#[cfg(unix)]
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::Session;
+use crate::accessibility::Accessibility;
+use crate::shortcuts::Shortcuts;
+use crate::widget::responsive::{Breakpoint, Breakpoints};
+use crate::{keyboard_nav, theme, Theme};
+use iced::window;
 
 /// Status of the nav bar and its panels.
 #[derive(Clone)]
@@ -22,13 +34,75 @@ pub struct Window {
     pub show_window_menu: bool,
     pub show_maximize: bool,
     pub show_minimize: bool,
+    fullscreen: bool,
+    maximized: bool,
     height: u32,
     width: u32,
 }
 
+impl Default for Window {
+    fn default() -> Self {
+        Self {
+            use_template: true,
+            can_fullscreen: false,
+            sharp_corners: false,
+            show_headerbar: true,
+            show_maximize: true,
+            show_minimize: true,
+            show_window_menu: false,
+            fullscreen: false,
+            maximized: false,
+            height: 0,
+            width: 0,
+        }
+    }
+}
+
+impl Window {
+    /// Whether the window is fullscreen.
+    #[must_use]
+    pub fn is_fullscreen(&self) -> bool {
+        self.fullscreen
+    }
+
+    /// Whether the window is maximized.
+    #[must_use]
+    pub fn is_maximized(&self) -> bool {
+        self.maximized
+    }
+
+    /// Tracks whether the window is maximized or fullscreen.
+    #[cfg(feature = "wayland")]
+    pub(crate) fn set_state(&mut self, maximized: bool, fullscreen: bool) {
+        self.maximized = maximized;
+        self.fullscreen = fullscreen;
+    }
+
+    /// The height of the window.
+    #[must_use]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The width of the window.
+    #[must_use]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+}
+
 /// COSMIC-specific application settings
 #[derive(Clone)]
 pub struct Core {
+    /// Breakpoint which the width of the main window falls in.
+    breakpoint: Breakpoint,
+
+    /// Minimum widths of the breakpoints of the main window.
+    breakpoints: Breakpoints,
+
+    /// Whether the context drawer is shown.
+    context_drawer: bool,
+
     /// Enables debug features in cosmic/iced.
     pub debug: bool,
 
@@ -38,12 +112,44 @@ pub struct Core {
     /// Current status of the nav bar panel.
     nav_bar: NavBar,
 
+    /// ID to assign to the next window or popup opened by the application.
+    next_surface_id: u128,
+
+    /// Whether the window and nav bar are restored when the application is launched again.
+    persist_session: bool,
+
+    /// Session restored on launch.
+    restored_session: Option<Session>,
+
+    /// Listens for the arguments of later launches when the application is single-instance.
+    #[cfg(unix)]
+    pub(crate) single_instance: Option<Arc<UnixListener>>,
+
+    /// Disables animations, overriding the accessibility settings of the system.
+    reduce_motion: Option<bool>,
+
     /// Scaling factor used by the application
     scale_factor: f32,
 
+    /// Whether the accessibility settings of the system disable animations.
+    system_reduce_motion: bool,
+
+    /// Bindings of the keyboard navigation shortcuts managed by libcosmic.
+    shortcuts: Shortcuts<keyboard_nav::Message>,
+
+    /// Duration of the cross-fade between system themes.
+    theme_transition: Duration,
+
     pub system_theme: Theme,
     pub(crate) title: String,
     pub window: Window,
+
+    /// Size of the main window when it was last resized while windowed.
+    windowed_size: Option<(u32, u32)>,
+
+    /// Windows opened by the application besides the main window.
+    windows: HashMap<window::Id, Window>,
+
     #[cfg(feature = "applet")]
     pub applet_helper: super::applet::CosmicAppletHelper,
 }
@@ -51,6 +157,9 @@ pub struct Core {
 impl Default for Core {
     fn default() -> Self {
         Self {
+            breakpoint: Breakpoint::default(),
+            breakpoints: Breakpoints::default(),
+            context_drawer: false,
             debug: false,
             is_condensed: false,
             nav_bar: NavBar {
@@ -58,20 +167,21 @@ impl Default for Core {
                 toggled: true,
                 toggled_condensed: true,
             },
+            next_surface_id: 1,
+            persist_session: false,
+            restored_session: None,
+            #[cfg(unix)]
+            single_instance: None,
+            reduce_motion: None,
             scale_factor: 1.0,
+            system_reduce_motion: false,
+            shortcuts: keyboard_nav::shortcuts(),
+            theme_transition: Duration::from_millis(300),
             system_theme: theme::theme(),
             title: String::new(),
-            window: Window {
-                use_template: true,
-                can_fullscreen: false,
-                sharp_corners: false,
-                show_headerbar: true,
-                show_maximize: true,
-                show_minimize: true,
-                show_window_menu: false,
-                height: 0,
-                width: 0,
-            },
+            window: Window::default(),
+            windowed_size: None,
+            windows: HashMap::new(),
             #[cfg(feature = "applet")]
             applet_helper: super::applet::CosmicAppletHelper::default(),
         }
@@ -79,12 +189,68 @@ impl Default for Core {
 }
 
 impl Core {
+    /// Breakpoint which the width of the main window falls in.
+    #[must_use]
+    pub fn breakpoint(&self) -> Breakpoint {
+        self.breakpoint
+    }
+
+    /// Minimum widths of the breakpoints of the main window.
+    #[must_use]
+    pub fn breakpoints(&self) -> Breakpoints {
+        self.breakpoints
+    }
+
+    /// Changes the minimum widths of the breakpoints of the main window.
+    pub fn set_breakpoints(&mut self, breakpoints: Breakpoints) {
+        self.breakpoints = breakpoints;
+        self.breakpoint_update();
+    }
+
     /// Whether the window is too small for the nav bar + main content.
     #[must_use]
     pub fn is_condensed(&self) -> bool {
         self.is_condensed
     }
 
+    /// Whether animations, such as cross-fades between themes, are disabled.
+    ///
+    /// Follows the [accessibility settings](crate::accessibility) of the system unless the
+    /// application overrides it with [`Core::set_reduce_motion`].
+    #[must_use]
+    pub fn reduce_motion(&self) -> bool {
+        self.reduce_motion.unwrap_or(self.system_reduce_motion)
+    }
+
+    /// Disables animations, such as cross-fades between themes, regardless of the
+    /// accessibility settings of the system.
+    pub fn set_reduce_motion(&mut self, reduce_motion: bool) {
+        self.reduce_motion = Some(reduce_motion);
+    }
+
+    /// Applies the accessibility settings of the system.
+    pub(crate) fn set_accessibility(&mut self, accessibility: &Accessibility) {
+        self.system_reduce_motion = accessibility.reduce_motion;
+    }
+
+    /// Duration of the cross-fade between system themes.
+    #[must_use]
+    pub fn theme_transition(&self) -> Duration {
+        self.theme_transition
+    }
+
+    /// Changes the duration of the cross-fade between system themes.
+    pub fn set_theme_transition(&mut self, duration: Duration) {
+        self.theme_transition = duration;
+    }
+
+    /// Whether changes to the system theme should be animated.
+    pub(crate) fn animate_theme_transition(&self) -> bool {
+        cfg!(any(feature = "tokio", feature = "smol"))
+            && !self.reduce_motion()
+            && !self.theme_transition.is_zero()
+    }
+
     /// The scaling factor used by the application.
     #[must_use]
     pub fn scale_factor(&self) -> f32 {
@@ -94,7 +260,21 @@ impl Core {
     /// Changes the scaling factor used by the application.
     pub(crate) fn set_scale_factor(&mut self, factor: f32) {
         self.scale_factor = factor;
-        self.is_condensed_update();
+        self.breakpoint_update();
+    }
+
+    /// Bindings of the keyboard navigation shortcuts managed by libcosmic.
+    #[must_use]
+    pub fn shortcuts(&self) -> &Shortcuts<keyboard_nav::Message> {
+        &self.shortcuts
+    }
+
+    /// Remaps the keyboard navigation shortcuts managed by libcosmic.
+    ///
+    /// Bindings remapped in the `shortcuts` key of the libcosmic config are applied when the
+    /// application starts, and again whenever the config changes.
+    pub fn shortcuts_mut(&mut self) -> &mut Shortcuts<keyboard_nav::Message> {
+        &mut self.shortcuts
     }
 
     /// Whether to show or hide the main window's content.
@@ -102,13 +282,32 @@ impl Core {
         !self.is_condensed || !self.nav_bar.toggled_condensed
     }
 
-    /// Call this whenever the scaling factor or window width has changed.
+    /// Call this whenever the breakpoints, scaling factor or window width has changed.
     #[allow(clippy::cast_precision_loss)]
-    fn is_condensed_update(&mut self) {
-        self.is_condensed = (600.0 * self.scale_factor) > self.window.width as f32;
+    fn breakpoint_update(&mut self) {
+        self.breakpoint = self
+            .breakpoints
+            .breakpoint(self.window.width as f32 / self.scale_factor);
+        self.is_condensed = self.breakpoint == Breakpoint::Narrow;
         self.nav_bar_update();
     }
 
+    /// Whether the context drawer is shown.
+    #[must_use]
+    pub fn context_drawer_active(&self) -> bool {
+        self.context_drawer
+    }
+
+    /// Shows or hides the context drawer.
+    pub fn set_context_drawer_active(&mut self, active: bool) {
+        self.context_drawer = active;
+    }
+
+    /// Toggles the visibility of the context drawer.
+    pub fn context_drawer_toggle(&mut self) {
+        self.context_drawer = !self.context_drawer;
+    }
+
     /// Whether the nav panel is visible or not
     #[must_use]
     pub fn nav_bar_active(&self) -> bool {
@@ -145,6 +344,169 @@ impl Core {
     /// Set the width of the main window.
     pub(crate) fn set_window_width(&mut self, new_width: u32) {
         self.window.width = new_width;
-        self.is_condensed_update();
+        self.breakpoint_update();
+    }
+
+    /// Settings and state of a window, including the main window.
+    #[must_use]
+    pub fn window_by_id(&self, id: window::Id) -> Option<&Window> {
+        if id == window::Id(0) {
+            Some(&self.window)
+        } else {
+            self.windows.get(&id)
+        }
+    }
+
+    /// Settings and state of a window, including the main window.
+    pub fn window_by_id_mut(&mut self, id: window::Id) -> Option<&mut Window> {
+        if id == window::Id(0) {
+            Some(&mut self.window)
+        } else {
+            self.windows.get_mut(&id)
+        }
+    }
+
+    /// IDs of the windows opened by the application besides the main window.
+    pub fn windows(&self) -> impl Iterator<Item = window::Id> + '_ {
+        self.windows.keys().copied()
+    }
+
+    /// Reserves an ID for a new surface, such as a popup, which no other window or popup of the
+    /// application is assigned.
+    pub fn next_surface_id(&mut self) -> window::Id {
+        let id = window::Id(self.next_surface_id);
+        self.next_surface_id += 1;
+        id
+    }
+
+    /// Reserves an ID for a new window, and begins tracking its state.
+    pub(crate) fn window_open(&mut self, width: u32, height: u32) -> window::Id {
+        let id = self.next_surface_id();
+        self.windows.insert(
+            id,
+            Window {
+                width,
+                height,
+                ..Window::default()
+            },
+        );
+        id
+    }
+
+    /// Stops tracking the state of a window which has been closed.
+    pub(crate) fn window_close(&mut self, id: window::Id) -> Option<Window> {
+        self.windows.remove(&id)
+    }
+
+    /// Updates the tracked size of a window.
+    pub(crate) fn window_resize(&mut self, id: window::Id, width: u32, height: u32) {
+        if id == window::Id(0) {
+            self.set_window_width(width);
+            self.set_window_height(height);
+            if !self.window.maximized && !self.window.fullscreen {
+                self.windowed_size = Some((width, height));
+            }
+        } else if let Some(window) = self.windows.get_mut(&id) {
+            window.width = width;
+            window.height = height;
+        }
+    }
+
+    /// Whether the window and nav bar are restored when the application is launched again.
+    #[must_use]
+    pub fn persist_session(&self) -> bool {
+        self.persist_session
+    }
+
+    /// Enables restoring the window and nav bar when the application is launched again.
+    pub(crate) fn set_persist_session(&mut self, persist: bool) {
+        self.persist_session = persist;
+    }
+
+    /// The session which was restored on launch.
+    pub(crate) fn restored_session(&self) -> Option<&Session> {
+        self.restored_session.as_ref()
+    }
+
+    /// Restores the main window's size and the nav bar from a previous session.
+    pub(crate) fn restore_session(&mut self, session: Session) {
+        self.nav_bar.toggled = session.nav_bar_toggled;
+        self.nav_bar.toggled_condensed = session.nav_bar_toggled_condensed;
+        self.set_window_width(session.size.0);
+        self.set_window_height(session.size.1);
+        self.restored_session = Some(session);
+    }
+
+    /// The session to persist for the main window and the nav bar.
+    ///
+    /// The size of the window is only updated while it is windowed, so that the window
+    /// is restored to its windowed size when it was closed while maximized.
+    pub(crate) fn session(&self) -> Session {
+        let mut session = self.restored_session.clone().unwrap_or_default();
+        if let Some(size) = self.windowed_size {
+            session.size = size;
+        }
+        session.maximized = self.window.maximized;
+        session.fullscreen = self.window.fullscreen;
+        session.nav_bar_toggled = self.nav_bar.toggled;
+        session.nav_bar_toggled_condensed = self.nav_bar.toggled_condensed;
+        session
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Core, Session};
+    use iced::window;
+
+    #[test]
+    fn test_restore_session() {
+        let mut core = Core::default();
+        core.restore_session(Session {
+            size: (800, 600),
+            nav_bar_toggled: false,
+            ..Session::default()
+        });
+
+        assert_eq!((core.window.width(), core.window.height()), (800, 600));
+        assert!(!core.nav_bar.toggled);
+        assert!(core.nav_bar.toggled_condensed);
+    }
+
+    #[test]
+    fn test_session_keeps_windowed_size() {
+        let mut core = Core::default();
+        core.restore_session(Session {
+            size: (800, 600),
+            nav_bar_position: Some(2),
+            ..Session::default()
+        });
+
+        core.window_resize(window::Id(0), 1000, 700);
+        let session = core.session();
+        assert_eq!(session.size, (1000, 700));
+        assert!(!session.maximized);
+        assert_eq!(session.nav_bar_position, Some(2));
+
+        // the size of a maximized window is not its windowed size
+        core.window.maximized = true;
+        core.window_resize(window::Id(0), 1920, 1080);
+        let session = core.session();
+        assert_eq!(session.size, (1000, 700));
+        assert!(session.maximized);
+
+        core.window.maximized = false;
+        core.window.fullscreen = true;
+        core.window_resize(window::Id(0), 2560, 1440);
+        let session = core.session();
+        assert_eq!(session.size, (1000, 700));
+        assert!(session.fullscreen);
+    }
+
+    #[test]
+    fn test_session_before_resize() {
+        // a window which was never resized keeps the default size
+        let core = Core::default();
+        assert_eq!(core.session().size, Session::default().size);
     }
 }
@@ -1,9 +1,12 @@
 // Copyright 2023 System76 <info@system76.com>
 // SPDX-License-Identifier: MPL-2.0
 
-use super::{command, Application, ApplicationExt, Core, Subscription};
-use crate::theme::{self, Theme, ThemeType, THEME};
+use super::{command, Application, ApplicationExt, Core, Session, Subscription};
+use crate::accessibility::{self, Accessibility};
+use crate::shortcuts::{self, Binding, Remaps};
+use crate::theme::{self, Theme, ThemeType, Transition, THEME};
 use crate::widget::nav_bar;
+use crate::widget::responsive::Breakpoint;
 use crate::{keyboard_nav, Element};
 #[cfg(feature = "wayland")]
 use iced::event::wayland::{self, WindowEvent};
@@ -16,20 +19,30 @@ use iced_runtime::command::Action;
 use iced_runtime::window::Action as WindowAction;
 #[cfg(feature = "wayland")]
 use sctk::reexports::csd_frame::{WindowManagerCapabilities, WindowState};
+use std::ffi::OsString;
+use std::time::Instant;
 
 /// A message managed internally by COSMIC.
 #[derive(Clone, Debug)]
 pub enum Message {
-    /// Requests to close the window.
-    Close,
-    /// Requests to drag the window.
-    Drag,
+    /// Notification of changes to the accessibility settings of the system.
+    AccessibilityChange(Accessibility),
+    /// The application was launched again with these arguments.
+    Activate(Vec<OsString>),
+    /// Requests to close a window, which exits the application if it is the main window.
+    Close(window::Id),
+    /// Shows or hides the context drawer.
+    ContextDrawer(bool),
+    /// Requests to drag a window.
+    Drag(window::Id),
     /// Keyboard shortcuts managed by libcosmic.
     KeyboardNav(keyboard_nav::Message),
-    /// Requests to maximize the window.
-    Maximize,
-    /// Requests to minimize the window.
-    Minimize,
+    /// A key binding which may trigger an action of the application or of libcosmic.
+    Shortcut(Binding),
+    /// Requests to maximize a window.
+    Maximize(window::Id),
+    /// Requests to minimize a window.
+    Minimize(window::Id),
     /// Activates a navigation element from the nav bar.
     NavBar(nav_bar::Id),
     /// Set scaling factor
@@ -38,6 +51,12 @@ pub enum Message {
     ThemeChange(Theme),
     /// Notification of system theme changes.
     SystemThemeChange(Theme),
+    /// Notification of bindings remapped in the libcosmic config.
+    RemapShortcuts(Remaps),
+    /// Notification of bindings remapped in the config of the application.
+    RemapAppShortcuts(Remaps),
+    /// Advances the cross-fade between system themes.
+    ThemeTransition(Instant),
     /// Toggles visibility of the nav bar.
     ToggleNavBar,
     /// Toggles the condensed status of the nav bar.
@@ -55,6 +74,7 @@ pub enum Message {
 #[derive(Default)]
 pub(crate) struct Cosmic<App> {
     pub(crate) app: App,
+    pub(crate) theme_transition: Option<Transition>,
     #[cfg(feature = "wayland")]
     pub(crate) should_exit: bool,
 }
@@ -70,15 +90,36 @@ where
 
     fn new((core, flags): Self::Flags) -> (Self, iced::Command<Self::Message>) {
         let (model, command) = T::init(core, flags);
+        let mut cosmic = Cosmic::new(model);
 
-        (Cosmic::new(model), command)
+        if let Some(shortcuts) = cosmic.app.shortcuts_mut() {
+            if let Err(err) = cosmic_config::Config::new(T::APP_ID, Remaps::version())
+                .and_then(|c| shortcuts.load(&c))
+            {
+                tracing::error!("failed to load shortcuts of {}: {:?}", T::APP_ID, err);
+            }
+        }
+
+        if let Some(shortcuts) = cosmic.app.shortcuts() {
+            let builtin = shortcuts.conflicts_with(cosmic.app.core().shortcuts());
+            for conflict in shortcuts.conflicts().into_iter().chain(builtin) {
+                tracing::warn!("conflicting shortcut: {}", conflict);
+            }
+        }
+
+        let restore = cosmic.restore_session();
+
+        (cosmic, iced::Command::batch([command, restore]))
     }
 
     #[cfg(feature = "wayland")]
     fn close_requested(&self, id: window::Id) -> Self::Message {
-        self.app
-            .on_close_requested(id)
-            .map_or(super::Message::None, super::Message::App)
+        // windows are closed unless the application intervenes, which exits the application
+        // after saving its session if it is the main window
+        self.app.on_close_requested(id).map_or(
+            super::Message::Cosmic(Message::Close(id)),
+            super::Message::App,
+        )
     }
 
     fn title(&self) -> String {
@@ -116,12 +157,24 @@ where
     }
 
     fn subscription(&self) -> Subscription<Self::Message> {
-        let window_events = iced::subscription::events_with(|event, _| {
+        let window_events = iced::subscription::events_with(|event, status| {
             match event {
+                iced::Event::Mouse(iced::mouse::Event::ButtonPressed { .. })
+                    if status == iced::event::Status::Ignored =>
+                {
+                    return Some(Message::KeyboardNav(keyboard_nav::Message::Unfocus));
+                }
+
                 iced::Event::Window(id, window::Event::Resized { width, height }) => {
                     return Some(Message::WindowResize(id, width, height));
                 }
 
+                // the session is saved before exiting, instead of iced exiting immediately
+                #[cfg(not(feature = "wayland"))]
+                iced::Event::Window(id, window::Event::CloseRequested) => {
+                    return Some(Message::Close(id));
+                }
+
                 #[cfg(feature = "wayland")]
                 iced::Event::PlatformSpecific(PlatformSpecific::Wayland(event)) => match event {
                     wayland::Event::Window(WindowEvent::State(state), _surface, id) => {
@@ -144,14 +197,45 @@ where
             None
         });
 
+        #[cfg(unix)]
+        let single_instance = match self.app.core().single_instance.clone() {
+            Some(listener) => super::single_instance::subscription(listener)
+                .map(Message::Activate)
+                .map(super::Message::Cosmic),
+            None => Subscription::none(),
+        };
+
+        #[cfg(not(unix))]
+        let single_instance = Subscription::none();
+
+        // the config of the application is only watched if it has shortcuts to remap
+        let app_remaps = if self.app.shortcuts().is_some() {
+            shortcuts::remaps_subscription(1, T::APP_ID, Remaps::version())
+                .map(Message::RemapAppShortcuts)
+                .map(super::Message::Cosmic)
+        } else {
+            Subscription::none()
+        };
+
         Subscription::batch(vec![
             self.app.subscription().map(super::Message::App),
-            keyboard_nav::subscription()
-                .map(Message::KeyboardNav)
+            single_instance,
+            shortcuts::subscription()
+                .map(Message::Shortcut)
+                .map(super::Message::Cosmic),
+            shortcuts::remaps_subscription(0, "com.system76.libcosmic", Remaps::version())
+                .map(Message::RemapShortcuts)
                 .map(super::Message::Cosmic),
+            app_remaps,
             theme::subscription(0)
                 .map(Message::SystemThemeChange)
                 .map(super::Message::Cosmic),
+            accessibility::subscription(0)
+                .map(Message::AccessibilityChange)
+                .map(super::Message::Cosmic),
+            self.theme_transition_subscription()
+                .map(Message::ThemeTransition)
+                .map(super::Message::Cosmic),
             window_events.map(super::Message::Cosmic),
         ])
     }
@@ -162,19 +246,21 @@ where
 
     #[cfg(feature = "wayland")]
     fn view(&self, id: window::Id) -> Element<Self::Message> {
-        if id != window::Id(0) {
-            return self.app.view_window(id).map(super::Message::App);
-        }
-        if self.app.core().window.use_template {
-            self.app.view_main()
-        } else {
-            self.app.view().map(super::Message::App)
-        }
+        self.view_themed(id, || {
+            if id != window::Id(0) {
+                return self.app.view_window(id).map(super::Message::App);
+            }
+            if self.app.core().window.use_template {
+                self.app.view_main()
+            } else {
+                self.app.view().map(super::Message::App)
+            }
+        })
     }
 
     #[cfg(not(feature = "wayland"))]
     fn view(&self) -> Element<Self::Message> {
-        self.app.view_main()
+        self.view_themed(window::Id(0), || self.app.view_main())
     }
 }
 
@@ -191,36 +277,121 @@ impl<T: Application> Cosmic<T> {
         iced::Command::single(Action::Window(WindowAction::Close))
     }
 
+    /// Notifies the application if the breakpoint of the main window is no longer `previous`.
+    fn breakpoint_changed(
+        &mut self,
+        previous: Breakpoint,
+    ) -> iced::Command<super::Message<T::Message>> {
+        let breakpoint = self.app.core().breakpoint();
+        if breakpoint == previous {
+            return iced::Command::none();
+        }
+
+        self.app.on_breakpoint(breakpoint)
+    }
+
+    /// Applies the theme of a window to its view, if the application overrides it.
+    fn view_themed<'a>(
+        &'a self,
+        id: window::Id,
+        view: impl FnOnce() -> Element<'a, super::Message<T::Message>>,
+    ) -> Element<'a, super::Message<T::Message>> {
+        match self.app.window_theme(id) {
+            Some(theme) => crate::widget::themed(theme, view)
+                .layer(cosmic_theme::Layer::Background)
+                .into(),
+            None => view(),
+        }
+    }
+
+    /// Restores the state of the main window and the active nav bar item from the last session.
+    fn restore_session(&mut self) -> iced::Command<super::Message<T::Message>> {
+        let Some(session) = self.app.core().restored_session().cloned() else {
+            return iced::Command::none();
+        };
+
+        let mut commands = Vec::new();
+
+        if session.fullscreen {
+            self.app.core_mut().window.sharp_corners = true;
+            commands.push(command::fullscreen());
+        } else if session.maximized {
+            commands.push(command::window_maximize(window::Id(0)));
+        }
+
+        let entity = session.nav_bar_position.and_then(|position| {
+            self.app
+                .nav_model()
+                .and_then(|model| model.iter().nth(usize::from(position)))
+        });
+
+        if let Some(entity) = entity {
+            commands.push(self.app.on_nav_select(entity));
+        }
+
+        iced::Command::batch(commands)
+    }
+
+    /// Stores the state of the main window and the active nav bar item, if enabled.
+    fn save_session(&self) {
+        let core = self.app.core();
+        if !core.persist_session() {
+            return;
+        }
+
+        let mut session = core.session();
+        session.nav_bar_position = self
+            .app
+            .nav_model()
+            .and_then(|model| model.position(model.active()));
+
+        if let Err(err) = Session::config(T::APP_ID).and_then(|config| session.save(&config)) {
+            tracing::error!("failed to save session: {:?}", err);
+        }
+    }
+
+    /// Redraws the cross-fade between system themes while it is in progress.
+    #[allow(clippy::unused_self)]
+    fn theme_transition_subscription(&self) -> Subscription<Instant> {
+        #[cfg(any(feature = "tokio", feature = "smol"))]
+        if self.theme_transition.is_some() {
+            return iced::time::every(std::time::Duration::from_millis(16));
+        }
+
+        Subscription::none()
+    }
+
     fn cosmic_update(&mut self, message: Message) -> iced::Command<super::Message<T::Message>> {
         match message {
             Message::WindowResize(id, width, height) => {
-                if window::Id(0) == id {
-                    self.app.core_mut().set_window_width(width);
-                    self.app.core_mut().set_window_height(height);
-                }
-
+                let breakpoint = self.app.core().breakpoint();
+                self.app.core_mut().window_resize(id, width, height);
                 self.app.on_window_resize(id, width, height);
+                return self.breakpoint_changed(breakpoint);
             }
 
             #[cfg(feature = "wayland")]
             Message::WindowState(id, state) => {
-                if window::Id(0) == id {
-                    self.app.core_mut().window.sharp_corners =
-                        matches!(state, WindowState::ACTIVATED)
-                            || state.contains(WindowState::TILED);
+                if let Some(window) = self.app.core_mut().window_by_id_mut(id) {
+                    window.sharp_corners = matches!(state, WindowState::ACTIVATED)
+                        || state.contains(WindowState::TILED);
+                    window.set_state(
+                        state.contains(WindowState::MAXIMIZED),
+                        state.contains(WindowState::FULLSCREEN),
+                    );
                 }
             }
 
             #[cfg(feature = "wayland")]
             Message::WmCapabilities(id, capabilities) => {
-                if window::Id(0) == id {
-                    self.app.core_mut().window.can_fullscreen =
+                if let Some(window) = self.app.core_mut().window_by_id_mut(id) {
+                    window.can_fullscreen =
                         capabilities.contains(WindowManagerCapabilities::FULLSCREEN);
-                    self.app.core_mut().window.show_maximize =
+                    window.show_maximize =
                         capabilities.contains(WindowManagerCapabilities::MAXIMIZE);
-                    self.app.core_mut().window.show_minimize =
+                    window.show_minimize =
                         capabilities.contains(WindowManagerCapabilities::MINIMIZE);
-                    self.app.core_mut().window.show_window_menu =
+                    window.show_window_menu =
                         capabilities.contains(WindowManagerCapabilities::WINDOW_MENU);
                 }
             }
@@ -241,23 +412,68 @@ impl<T: Application> Cosmic<T> {
                 keyboard_nav::Message::Fullscreen => return command::toggle_fullscreen(),
             },
 
-            Message::Drag => return command::drag(),
+            Message::Shortcut(binding) => {
+                let app_message = self
+                    .app
+                    .shortcuts()
+                    .and_then(|shortcuts| shortcuts.action_for(&binding))
+                    .cloned();
+
+                if let Some(message) = app_message {
+                    return self.app.update(message);
+                }
+
+                if let Some(message) = self.app.core().shortcuts().action_for(&binding) {
+                    return self.cosmic_update(Message::KeyboardNav(*message));
+                }
+            }
+
+            Message::RemapShortcuts(remaps) => {
+                self.app.core_mut().shortcuts_mut().remap(&remaps);
+            }
+
+            Message::RemapAppShortcuts(remaps) => {
+                if let Some(shortcuts) = self.app.shortcuts_mut() {
+                    shortcuts.remap(&remaps);
+                }
+            }
+
+            Message::AccessibilityChange(accessibility) => {
+                self.app.core_mut().set_accessibility(&accessibility);
+            }
+
+            Message::Activate(args) => return self.app.on_activate(args),
+
+            Message::Drag(id) => return command::window_drag(id),
+
+            #[cfg(feature = "wayland")]
+            Message::Close(id) if id != window::Id(0) => return self.app.close_window(id),
+
+            Message::Close(_) => {
+                self.save_session();
+
+                #[cfg(unix)]
+                if self.app.core().single_instance.is_some() {
+                    super::single_instance::release(T::APP_ID);
+                }
 
-            Message::Close => {
                 self.app.on_app_exit();
                 return self.close();
             }
 
-            Message::Minimize => return command::minimize(),
+            Message::Minimize(id) => return command::window_minimize(id),
 
-            Message::Maximize => {
-                if self.app.core().window.sharp_corners {
-                    self.app.core_mut().window.sharp_corners = false;
-                    return command::set_windowed();
-                }
+            Message::Maximize(id) => {
+                let Some(window) = self.app.core_mut().window_by_id_mut(id) else {
+                    return iced::Command::none();
+                };
 
-                self.app.core_mut().window.sharp_corners = true;
-                return command::fullscreen();
+                window.sharp_corners = !window.sharp_corners;
+                return if window.sharp_corners {
+                    command::window_fullscreen(id)
+                } else {
+                    command::window_set_windowed(id)
+                };
             }
 
             Message::NavBar(key) => {
@@ -265,6 +481,10 @@ impl<T: Application> Cosmic<T> {
                 return self.app.on_nav_select(key);
             }
 
+            Message::ContextDrawer(active) => {
+                self.app.core_mut().set_context_drawer_active(active);
+            }
+
             Message::ToggleNavBar => {
                 self.app.core_mut().nav_bar_toggle();
             }
@@ -274,6 +494,8 @@ impl<T: Application> Cosmic<T> {
             }
 
             Message::ThemeChange(theme) => {
+                self.theme_transition = None;
+
                 // our system theme is always receiving updates so we should use it instead
                 let theme = if matches!(theme.theme_type, ThemeType::System(_)) {
                     self.app.core().system_theme.clone()
@@ -288,15 +510,48 @@ impl<T: Application> Cosmic<T> {
             }
 
             Message::ScaleFactor(factor) => {
+                let breakpoint = self.app.core().breakpoint();
                 self.app.core_mut().set_scale_factor(factor);
+                return self.breakpoint_changed(breakpoint);
             }
             Message::SystemThemeChange(theme) => {
                 self.app.core_mut().system_theme = theme.clone();
-                THEME.with(move |t| {
+                let animate = self.app.core().animate_theme_transition();
+                let duration = self.app.core().theme_transition();
+                THEME.with(|t| {
                     let mut cosmic_theme = t.borrow_mut();
                     // only apply update if the theme is set to load a system theme
+                    let ThemeType::System(ref current) = cosmic_theme.theme_type else {
+                        return;
+                    };
+
+                    match theme.theme_type {
+                        ThemeType::System(target) if animate => {
+                            self.theme_transition =
+                                Some(Transition::new(current.clone(), target, duration));
+                        }
+                        theme_type => {
+                            self.theme_transition = None;
+                            cosmic_theme.set_theme(theme_type);
+                        }
+                    }
+                });
+            }
+
+            Message::ThemeTransition(now) => {
+                let Some(transition) = self.theme_transition.as_ref() else {
+                    return iced::Command::none();
+                };
+
+                let theme = transition.theme(now);
+                if transition.is_finished(now) {
+                    self.theme_transition = None;
+                }
+
+                THEME.with(move |t| {
+                    let mut cosmic_theme = t.borrow_mut();
                     if matches!(cosmic_theme.theme_type, ThemeType::System(_)) {
-                        cosmic_theme.set_theme(theme.theme_type);
+                        cosmic_theme.set_theme(ThemeType::System(theme));
                     }
                 });
             }
@@ -310,6 +565,7 @@ impl<App: Application> Cosmic<App> {
     pub fn new(app: App) -> Self {
         Self {
             app,
+            theme_transition: None,
             #[cfg(feature = "wayland")]
             should_exit: false,
         }
@@ -11,7 +11,10 @@ pub mod applet;
 pub mod command;
 mod core;
 pub mod cosmic;
+mod session;
 pub mod settings;
+#[cfg(unix)]
+mod single_instance;
 
 pub mod message {
     #[derive(Clone, Debug)]
@@ -40,9 +43,13 @@ pub mod message {
 
 pub use self::command::Command;
 pub use self::core::Core;
-pub use self::settings::Settings;
+pub use self::session::Session;
+pub use self::settings::{Settings, WindowSettings};
+use crate::shortcuts::Shortcuts;
 use crate::theme::THEME;
+use crate::widget::context_drawer::ContextDrawer;
 use crate::widget::nav_bar;
+use crate::widget::responsive::{Breakpoint, Breakpoints};
 use crate::{Element, ElementExt};
 use apply::Apply;
 use iced::Subscription;
@@ -54,16 +61,48 @@ pub use message::Message;
 /// # Errors
 ///
 /// Returns error on application failure.
-pub fn run<App: Application>(settings: Settings, flags: App::Flags) -> iced::Result {
+pub fn run<App: Application>(mut settings: Settings, flags: App::Flags) -> iced::Result {
     if let Some(icon_theme) = settings.default_icon_theme {
         crate::icon_theme::set_default(icon_theme);
     }
 
     let mut core = Core::default();
     core.debug = settings.debug;
+    core.set_breakpoints(settings.breakpoints);
     core.set_scale_factor(settings.scale_factor);
+    core.set_accessibility(&crate::accessibility::accessibility());
+    if let Some(reduce_motion) = settings.reduce_motion {
+        core.set_reduce_motion(reduce_motion);
+    }
+    core.set_theme_transition(settings.theme_transition);
     core.set_window_width(settings.size.0);
     core.set_window_height(settings.size.1);
+
+    if let Err(err) = cosmic_config::Config::libcosmic().and_then(|c| core.shortcuts_mut().load(&c))
+    {
+        tracing::error!("failed to load keyboard navigation shortcuts: {:?}", err);
+    }
+
+    #[cfg(unix)]
+    if settings.single_instance {
+        let args: Vec<std::ffi::OsString> = std::env::args_os().skip(1).collect();
+        match single_instance::acquire(App::APP_ID, &args) {
+            Ok(single_instance::Instance::Primary(listener)) => {
+                core.single_instance = Some(listener);
+            }
+            Ok(single_instance::Instance::Forwarded) => return Ok(()),
+            Err(err) => tracing::error!("failed to become the single instance: {:?}", err),
+        }
+    }
+
+    if settings.persist_session {
+        core.set_persist_session(true);
+        if let Ok(session) = Session::config(App::APP_ID).and_then(|c| Session::load(&c)) {
+            settings.size = session.size;
+            core.restore_session(session);
+        }
+    }
+
     THEME.with(move |t| {
         let mut cosmic_theme = t.borrow_mut();
         cosmic_theme.set_theme(settings.theme.theme_type);
@@ -106,6 +145,8 @@ pub fn run<App: Application>(settings: Settings, flags: App::Flags) -> iced::Res
         iced.window.decorations = !settings.client_decorations;
         iced.window.size = settings.size;
         iced.window.transparent = settings.transparent;
+        // closing the window is handled by `Message::Close`, which saves the session
+        iced.exit_on_close_request = false;
     }
 
     cosmic::Cosmic::<App>::run(iced)
@@ -140,6 +181,24 @@ where
     /// Creates the application, and optionally emits command on initialize.
     fn init(core: Core, flags: Self::Flags) -> (Self, iced::Command<Message<Self::Message>>);
 
+    /// Shows details of the selected content in a panel on the right side of the main window,
+    /// such as [`crate::widget::context_drawer`], while
+    /// [`Core::context_drawer_active`] is set.
+    ///
+    /// The header of the drawer has a close button which hides it. When the window is condensed,
+    /// the drawer is shown over the content instead.
+    fn context_drawer(&self) -> Option<ContextDrawer<Self::Message>> {
+        None
+    }
+
+    /// Shows a modal dialog over the main window, such as [`crate::widget::dialog`].
+    ///
+    /// The rest of the window is blocked until the dialog is dismissed by returning `None`,
+    /// which should also be done by [`Application::on_escape`].
+    fn dialog(&self) -> Option<Element<Self::Message>> {
+        None
+    }
+
     /// Attaches elements to the start section of the header.
     fn header_start(&self) -> Vec<Element<Self::Message>> {
         Vec::new()
@@ -160,6 +219,17 @@ where
         None
     }
 
+    /// Called when the application is launched again while it is running, with the arguments
+    /// of the new launch, if it is single-instance. This is where a new window or tab is opened.
+    ///
+    /// Arguments are not required to be UTF-8, as they are often paths.
+    fn on_activate(
+        &mut self,
+        args: Vec<std::ffi::OsString>,
+    ) -> iced::Command<Message<Self::Message>> {
+        iced::Command::none()
+    }
+
     /// Called before closing the application.
     fn on_app_exit(&mut self) {}
 
@@ -168,6 +238,11 @@ where
         None
     }
 
+    /// Called when the width of the main window crosses one of its [`Breakpoints`].
+    fn on_breakpoint(&mut self, breakpoint: Breakpoint) -> iced::Command<Message<Self::Message>> {
+        iced::Command::none()
+    }
+
     /// Called when the escape key is pressed.
     fn on_escape(&mut self) -> iced::Command<Message<Self::Message>> {
         iced::Command::none()
@@ -183,9 +258,25 @@ where
         iced::Command::none()
     }
 
+    /// Called after a window other than the main window has been closed.
+    fn on_window_close(&mut self, id: window::Id) {}
+
     /// Called when a window is resized.
     fn on_window_resize(&mut self, id: window::Id, width: u32, height: u32) {}
 
+    /// Actions of the application which are triggered by key bindings.
+    ///
+    /// These take precedence over the keyboard navigation shortcuts of the [`Core`].
+    fn shortcuts(&self) -> Option<&Shortcuts<Self::Message>> {
+        None
+    }
+
+    /// Mutable access to the [`Application::shortcuts`], through which the bindings remapped in
+    /// the `shortcuts` key of the config of [`Application::APP_ID`] are applied.
+    fn shortcuts_mut(&mut self) -> Option<&mut Shortcuts<Self::Message>> {
+        None
+    }
+
     /// Event sources that are to be listened to.
     fn subscription(&self) -> Subscription<Self::Message> {
         Subscription::none()
@@ -199,19 +290,28 @@ where
     /// Constructs the view for the main window.
     fn view(&self) -> Element<Self::Message>;
 
-    /// Constructs views for other windows.
+    /// Constructs views for other windows, which are opened with [`ApplicationExt::open_window`].
     fn view_window(&self, id: window::Id) -> Element<Self::Message> {
-        panic!("no view for window {}", id.0);
+        crate::widget::Space::new(iced::Length::Fill, iced::Length::Fill).into()
     }
 
     /// Overrides the default style for applications
     fn style(&self) -> Option<<crate::Theme as iced_style::application::StyleSheet>::Style> {
         None
     }
+
+    /// Overrides the theme of a window, which otherwise uses the theme of the application.
+    fn window_theme(&self, id: window::Id) -> Option<crate::Theme> {
+        None
+    }
 }
 
 /// Methods automatically derived for all types implementing [`Application`].
 pub trait ApplicationExt: Application {
+    /// Closes a window which was opened with [`ApplicationExt::open_window`].
+    #[cfg(feature = "wayland")]
+    fn close_window(&mut self, id: window::Id) -> iced::Command<Message<Self::Message>>;
+
     /// Initiates a window drag.
     fn drag(&mut self) -> iced::Command<Message<Self::Message>>;
 
@@ -221,6 +321,15 @@ pub trait ApplicationExt: Application {
     /// Minimizes the window.
     fn minimize(&mut self) -> iced::Command<Message<Self::Message>>;
 
+    /// Opens a new window, which is drawn by [`Application::view_window`].
+    ///
+    /// Returns the ID of the window, whose size and state are available from [`Core::window_by_id`].
+    #[cfg(feature = "wayland")]
+    fn open_window(
+        &mut self,
+        settings: WindowSettings,
+    ) -> (window::Id, iced::Command<Message<Self::Message>>);
+
     /// Get the title of the main window.
     fn title(&self) -> &str;
 
@@ -232,6 +341,16 @@ pub trait ApplicationExt: Application {
 }
 
 impl<App: Application> ApplicationExt for App {
+    #[cfg(feature = "wayland")]
+    fn close_window(&mut self, id: window::Id) -> iced::Command<Message<Self::Message>> {
+        if self.core_mut().window_close(id).is_none() {
+            return iced::Command::none();
+        }
+
+        self.on_window_close(id);
+        command::window_close(id)
+    }
+
     fn drag(&mut self) -> iced::Command<Message<Self::Message>> {
         command::drag()
     }
@@ -244,6 +363,32 @@ impl<App: Application> ApplicationExt for App {
         command::minimize()
     }
 
+    #[cfg(feature = "wayland")]
+    fn open_window(
+        &mut self,
+        settings: WindowSettings,
+    ) -> (window::Id, iced::Command<Message<Self::Message>>) {
+        use iced::wayland::actions::window::SctkWindowSettings;
+
+        let (width, height) = settings.size;
+        let id = self.core_mut().window_open(width, height);
+        let command = crate::command::window_open(SctkWindowSettings {
+            window_id: id,
+            app_id: Some(Self::APP_ID.to_owned()),
+            autosize: settings.autosize,
+            client_decorations: settings.client_decorations,
+            parent: settings.parent,
+            resizable: settings.resizable,
+            size: settings.size,
+            size_limits: settings.size_limits,
+            title: settings.title,
+            transparent: settings.transparent,
+            ..SctkWindowSettings::default()
+        });
+
+        (id, command)
+    }
+
     fn title(&self) -> &str {
         &self.core().title
     }
@@ -270,8 +415,8 @@ impl<App: Application> ApplicationExt for App {
             main.push({
                 let mut header = crate::widget::header_bar()
                     .title(self.title())
-                    .on_drag(Message::Cosmic(cosmic::Message::Drag))
-                    .on_close(Message::Cosmic(cosmic::Message::Close));
+                    .on_drag(Message::Cosmic(cosmic::Message::Drag(window::Id(0))))
+                    .on_close(Message::Cosmic(cosmic::Message::Close(window::Id(0))));
 
                 if self.nav_model().is_some() {
                     let toggle = crate::widget::nav_bar_toggle()
@@ -286,11 +431,13 @@ impl<App: Application> ApplicationExt for App {
                 }
 
                 if core.window.show_maximize {
-                    header = header.on_maximize(Message::Cosmic(cosmic::Message::Maximize));
+                    header = header
+                        .on_maximize(Message::Cosmic(cosmic::Message::Maximize(window::Id(0))));
                 }
 
                 if core.window.show_minimize {
-                    header = header.on_minimize(Message::Cosmic(cosmic::Message::Minimize));
+                    header = header
+                        .on_minimize(Message::Cosmic(cosmic::Message::Minimize(window::Id(0))));
                 }
 
                 for element in self.header_start() {
@@ -309,43 +456,73 @@ impl<App: Application> ApplicationExt for App {
             });
         }
 
-        // The content element contains every element beneath the header.
-        main.push(
-            iced::widget::row({
-                let mut widgets = Vec::with_capacity(2);
-
-                // Insert nav bar onto the left side of the window.
-                if core.nav_bar_active() {
-                    if let Some(nav_model) = self.nav_model() {
-                        let mut nav = crate::widget::nav_bar(nav_model, |entity| {
-                            Message::Cosmic(cosmic::Message::NavBar(entity))
-                        });
+        let context_drawer = if core.context_drawer_active() {
+            self.context_drawer().map(|drawer| {
+                drawer
+                    .map(Message::App)
+                    .on_close(Message::Cosmic(cosmic::Message::ContextDrawer(false)))
+                    .apply(Element::from)
+                    .debug(core.debug)
+            })
+        } else {
+            None
+        };
 
-                        if !is_condensed {
-                            nav = nav.max_width(300);
-                        }
+        // The drawer is shown over the content when there is no room beside it.
+        let (context_drawer, condensed_drawer) = if is_condensed {
+            (None, context_drawer)
+        } else {
+            (context_drawer, None)
+        };
 
-                        widgets.push(nav.apply(Element::from).debug(core.debug));
+        // The content element contains every element beneath the header.
+        let content: Element<_> = iced::widget::row({
+            let mut widgets = Vec::with_capacity(3);
+
+            // Insert nav bar onto the left side of the window.
+            if core.nav_bar_active() {
+                if let Some(nav_model) = self.nav_model() {
+                    let mut nav = crate::widget::nav_bar(nav_model, |entity| {
+                        Message::Cosmic(cosmic::Message::NavBar(entity))
+                    });
+
+                    if !is_condensed {
+                        nav = nav.max_width(300);
                     }
-                }
 
-                if self.nav_model().is_none() || core.show_content() {
-                    let main_content = self.view().debug(core.debug).map(Message::App);
-
-                    widgets.push(main_content);
+                    widgets.push(nav.apply(Element::from).debug(core.debug));
                 }
-
-                widgets
-            })
-            .spacing(8)
-            .apply(iced::widget::container)
-            .padding([0, 8, 8, 8])
-            .width(iced::Length::Fill)
-            .height(iced::Length::Fill)
-            .style(crate::theme::Container::Background)
-            .into(),
-        );
-
-        iced::widget::column(main).into()
+            }
+
+            if self.nav_model().is_none() || core.show_content() {
+                widgets.push(self.view().debug(core.debug).map(Message::App));
+            }
+
+            if let Some(drawer) = context_drawer {
+                widgets.push(drawer);
+            }
+
+            widgets
+        })
+        .spacing(8)
+        .apply(iced::widget::container)
+        .padding([0, 8, 8, 8])
+        .width(iced::Length::Fill)
+        .height(iced::Length::Fill)
+        .style(crate::theme::Container::Background)
+        .into();
+
+        // The condensed drawer covers the nav bar too, so that it is shown whether or not the
+        // content is.
+        main.push(match condensed_drawer {
+            Some(drawer) => crate::widget::dialog::modal(content, Some(drawer))
+                .align_x(iced::alignment::Horizontal::Right)
+                .on_scrim_press(Message::Cosmic(cosmic::Message::ContextDrawer(false)))
+                .into(),
+            None => content,
+        });
+
+        let dialog = self.dialog().map(|dialog| dialog.map(Message::App));
+        crate::widget::dialog::modal(iced::widget::column(main), dialog).into()
     }
 }
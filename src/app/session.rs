@@ -0,0 +1,143 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Restore the window and nav bar of an application as the user left them.
+
+use cosmic_config::{Config, ConfigGet, ConfigSet};
+use serde::{Deserialize, Serialize};
+
+/// Key of the session in the config of the application.
+const SESSION_KEY: &str = "session";
+
+/// State of the main window which is persisted between launches of an application.
+///
+/// Enabled with [`Settings::persist_session`](super::Settings::persist_session), which stores
+/// the session in the config of the application's `APP_ID` when the application exits.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Session {
+    /// Size of the main window while it was windowed.
+    pub size: (u32, u32),
+    /// Whether the main window was maximized.
+    pub maximized: bool,
+    /// Whether the main window was fullscreen.
+    pub fullscreen: bool,
+    /// Whether the nav bar was toggled on.
+    pub nav_bar_toggled: bool,
+    /// Whether the nav bar was toggled on while the window was condensed.
+    pub nav_bar_toggled_condensed: bool,
+    /// Position of the active item of the nav bar.
+    pub nav_bar_position: Option<u16>,
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Self {
+            size: (1024, 768),
+            maximized: false,
+            fullscreen: false,
+            nav_bar_toggled: true,
+            nav_bar_toggled_condensed: true,
+            nav_bar_position: None,
+        }
+    }
+}
+
+impl Session {
+    /// Version of the session config.
+    #[must_use]
+    pub fn version() -> u64 {
+        1
+    }
+
+    /// The config which the session of an application is stored in.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config directory could not be found.
+    pub fn config(app_id: &str) -> Result<Config, cosmic_config::Error> {
+        Config::new(app_id, Self::version())
+    }
+
+    /// Loads the session stored in a config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no session was stored, or it could not be read.
+    pub fn load(config: &Config) -> Result<Self, cosmic_config::Error> {
+        config.get(SESSION_KEY)
+    }
+
+    /// Stores the session in a config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the session could not be written.
+    pub fn save(&self, config: &Config) -> Result<(), cosmic_config::Error> {
+        config.set(SESSION_KEY, self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Session, SESSION_KEY};
+    use cosmic_config::Config;
+
+    const APP_ID: &str = "com.system76.Test";
+
+    fn config(test_name: &str) -> (Config, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!(
+            "libcosmic-test-{}-{}",
+            std::process::id(),
+            test_name
+        ));
+        let config = Config::with_custom_path(APP_ID, Session::version(), path.clone()).unwrap();
+        (config, path)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let (config, path) = config("session-round-trip");
+        let session = Session {
+            size: (800, 600),
+            maximized: true,
+            fullscreen: false,
+            nav_bar_toggled: false,
+            nav_bar_toggled_condensed: true,
+            nav_bar_position: Some(2),
+        };
+        session.save(&config).unwrap();
+        let loaded = Session::load(&config);
+        let _ = std::fs::remove_dir_all(path);
+
+        assert_eq!(loaded.unwrap(), session);
+    }
+
+    #[test]
+    fn test_missing() {
+        let (config, path) = config("session-missing");
+        let loaded = Session::load(&config);
+        let _ = std::fs::remove_dir_all(path);
+
+        assert!(loaded.is_err());
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        // sessions written by older versions still load, with defaults for new fields
+        let (config, path) = config("session-missing-fields");
+        let dir = path.join(APP_ID).join(format!("v{}", Session::version()));
+        std::fs::write(dir.join(SESSION_KEY), "(size: (800, 600), maximized: true)").unwrap();
+        let loaded = Session::load(&config);
+        let _ = std::fs::remove_dir_all(path);
+
+        assert_eq!(
+            loaded.unwrap(),
+            Session {
+                size: (800, 600),
+                maximized: true,
+                ..Session::default()
+            }
+        );
+    }
+}
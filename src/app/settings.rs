@@ -3,10 +3,12 @@
 
 //! Configure a new COSMIC application.
 
+use crate::widget::responsive::Breakpoints;
 use crate::{font, Theme};
 #[cfg(feature = "wayland")]
 use iced::Limits;
 use iced_core::Font;
+use std::time::Duration;
 
 /// Configure a new COSMIC application.
 #[allow(clippy::struct_excessive_bools)]
@@ -23,6 +25,10 @@ pub struct Settings {
     #[cfg(feature = "wayland")]
     pub(crate) no_main_window: bool,
 
+    /// Minimum widths of the main window's breakpoints, the narrowest of which condenses the
+    /// nav bar.
+    pub(crate) breakpoints: Breakpoints,
+
     /// Whether the window should have a border, a title bar, etc. or not.
     pub(crate) client_decorations: bool,
 
@@ -39,10 +45,23 @@ pub struct Settings {
     /// Default size of fonts.
     pub(crate) default_text_size: f32,
 
+    /// Restores the size of the main window and the state of the nav bar from the last time
+    /// that the application was closed.
+    pub(crate) persist_session: bool,
+
+    /// Disables animations, such as cross-fades between themes, overriding the
+    /// [accessibility settings](crate::accessibility) of the system.
+    #[setters(strip_option)]
+    pub(crate) reduce_motion: Option<bool>,
+
     /// Whether the window should be resizable or not.
     /// and the size of the window border which can be dragged for a resize
     pub(crate) resizable: Option<f64>,
 
+    /// Forwards the arguments of later launches to the running instance of the application,
+    /// which receives them in [`Application::on_activate`](super::Application::on_activate).
+    pub(crate) single_instance: bool,
+
     /// Scale factor to use by default.
     pub(crate) scale_factor: f32,
 
@@ -56,6 +75,9 @@ pub struct Settings {
     /// The theme to apply to the application.
     pub(crate) theme: Theme,
 
+    /// Duration of the cross-fade between system themes.
+    pub(crate) theme_transition: Duration,
+
     /// Whether the window should be transparent.
     pub(crate) transparent: bool,
 }
@@ -71,27 +93,98 @@ impl Settings {
 
 impl Default for Settings {
     fn default() -> Self {
+        let theme = crate::theme::theme();
+        let typography = &theme.cosmic().typography;
+        // translucent themes need a transparent surface to show what is behind the window
+        let transparent = theme.cosmic().translucency().is_translucent();
+
         Self {
             antialiasing: true,
             #[cfg(feature = "wayland")]
             autosize: false,
             #[cfg(feature = "wayland")]
             no_main_window: false,
+            breakpoints: Breakpoints::default(),
             client_decorations: true,
             debug: false,
-            default_font: font::FONT,
+            default_font: font::from_text_style(typography, &typography.body),
             default_icon_theme: Some(String::from("Cosmic")),
-            default_text_size: 14.0,
+            default_text_size: typography.body.size,
+            persist_session: false,
+            reduce_motion: None,
             resizable: Some(8.0),
             scale_factor: std::env::var("COSMIC_SCALE")
                 .ok()
                 .and_then(|scale| scale.parse::<f32>().ok())
                 .unwrap_or(1.0),
+            single_instance: false,
             size: (1024, 768),
             #[cfg(feature = "wayland")]
             size_limits: Limits::NONE.min_height(1.0).min_width(1.0),
-            theme: crate::theme::theme(),
-            transparent: false,
+            theme,
+            theme_transition: Duration::from_millis(300),
+            transparent,
+        }
+    }
+}
+
+/// Configure a window opened by a COSMIC application after it has started.
+#[derive(derive_setters::Setters)]
+pub struct WindowSettings {
+    /// Autosize the window to fit its contents
+    #[cfg(feature = "wayland")]
+    pub(crate) autosize: bool,
+
+    /// Whether the window should have a border, a title bar, etc. or not.
+    pub(crate) client_decorations: bool,
+
+    /// The window which the new window belongs to, such as the main window of a dialog.
+    #[setters(strip_option)]
+    pub(crate) parent: Option<iced::window::Id>,
+
+    /// Whether the window should be resizable or not.
+    /// and the size of the window border which can be dragged for a resize
+    pub(crate) resizable: Option<f64>,
+
+    /// Initial size of the window.
+    pub(crate) size: (u32, u32),
+
+    /// Limitations of the window size
+    #[cfg(feature = "wayland")]
+    pub(crate) size_limits: Limits,
+
+    /// Title of the window.
+    #[setters(skip)]
+    pub(crate) title: Option<String>,
+
+    /// Whether the window should be transparent.
+    pub(crate) transparent: bool,
+}
+
+impl WindowSettings {
+    /// Sets the title of the window.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        let transparent =
+            crate::theme::THEME.with(|t| t.borrow().cosmic().translucency().is_translucent());
+
+        Self {
+            #[cfg(feature = "wayland")]
+            autosize: false,
+            client_decorations: true,
+            parent: None,
+            resizable: Some(8.0),
+            size: (640, 480),
+            #[cfg(feature = "wayland")]
+            size_limits: Limits::NONE.min_height(1.0).min_width(1.0),
+            title: None,
+            transparent,
         }
     }
 }
@@ -0,0 +1,133 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Forward the arguments of additional launches of an application to its running instance.
+
+use iced::futures::{channel::mpsc, SinkExt, StreamExt};
+use iced::Subscription;
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Whether this process is the running instance of the application.
+pub(crate) enum Instance {
+    /// This is the first instance, which listens for the arguments of later launches.
+    Primary(Arc<UnixListener>),
+    /// The arguments were forwarded to the running instance, so this process should exit.
+    Forwarded,
+}
+
+/// Path of the socket which the running instance of an application listens on.
+///
+/// The socket is only created in the runtime directory of the user, which other users can not
+/// access, so that they can not listen in place of the application.
+fn socket_path(app_id: &str) -> io::Result<PathBuf> {
+    std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| PathBuf::from(dir).join(format!("{app_id}.instance")))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "XDG_RUNTIME_DIR is not set"))
+}
+
+/// Takes an exclusive lock on a file next to the socket, which is held until the file is dropped.
+///
+/// Launches wait on the lock, so that only one of them at a time may replace the socket.
+fn lock(path: &Path) -> io::Result<File> {
+    let mut lock_path = path.as_os_str().to_owned();
+    lock_path.push(".lock");
+    let file = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(lock_path)?;
+
+    // SAFETY: the file descriptor is owned by `file`, which outlives the call.
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(file)
+}
+
+/// Forwards `args` to the running instance of the application, or becomes the running instance.
+///
+/// # Errors
+///
+/// Returns an error if the socket could neither be connected to nor created, or if there is no
+/// runtime directory to create it in.
+pub(crate) fn acquire(app_id: &str, args: &[OsString]) -> io::Result<Instance> {
+    let path = socket_path(app_id)?;
+    // without the lock, two launches may both fail to connect, and the second would remove the
+    // socket which the first just bound
+    let _lock = lock(&path)?;
+
+    if let Ok(mut stream) = UnixStream::connect(&path) {
+        // arguments are separated by nul bytes, which they may not contain
+        for arg in args {
+            stream.write_all(arg.as_bytes())?;
+            stream.write_all(&[0])?;
+        }
+        return Ok(Instance::Forwarded);
+    }
+
+    // the socket of an instance which did not exit cleanly is left behind
+    let _res = std::fs::remove_file(&path);
+    UnixListener::bind(&path).map(|listener| Instance::Primary(Arc::new(listener)))
+}
+
+/// Removes the socket of the running instance when it exits.
+pub(crate) fn release(app_id: &str) {
+    if let Ok(path) = socket_path(app_id) {
+        let _res = std::fs::remove_file(path);
+    }
+}
+
+/// Emits the arguments of each launch which was forwarded to the running instance.
+pub(crate) fn subscription(listener: Arc<UnixListener>) -> Subscription<Vec<OsString>> {
+    let type_id = std::any::TypeId::of::<Instance>();
+
+    iced::subscription::channel(type_id, 10, move |mut output| async move {
+        // launches are queued until they are handled, rather than dropped
+        let (tx, mut rx) = mpsc::unbounded();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut data = Vec::new();
+                // a client which never closes its end must not stall later launches
+                let read = stream.and_then(|mut stream| {
+                    stream.set_read_timeout(Some(Duration::from_secs(1)))?;
+                    stream.read_to_end(&mut data)
+                });
+                let Ok(_) = read else {
+                    continue;
+                };
+
+                let args = match data.strip_suffix(&[0]) {
+                    Some(data) => data
+                        .split(|&byte| byte == 0)
+                        .map(|arg| OsString::from_vec(arg.to_vec()))
+                        .collect(),
+                    None => Vec::new(),
+                };
+
+                if tx.unbounded_send(args).is_err() {
+                    break;
+                }
+            }
+        });
+
+        loop {
+            match rx.next().await {
+                Some(args) => {
+                    let _res = output.send(args).await;
+                }
+                None => iced::futures::future::pending().await,
+            }
+        }
+    })
+}
@@ -4,16 +4,23 @@
 //! Create asynchronous actions to be performed in the background.
 
 #[cfg(feature = "wayland")]
+use crate::widget::menu::ContextMenuPopup;
+#[cfg(feature = "wayland")]
+use iced::wayland::actions::window::SctkWindowSettings;
 use iced::window;
 use iced::Command;
 use iced_core::window::Mode;
 #[cfg(feature = "wayland")]
+use iced_runtime::command::platform_specific::wayland::popup::{SctkPopupSettings, SctkPositioner};
+#[cfg(feature = "wayland")]
 use iced_runtime::command::platform_specific::wayland::window::Action as WindowAction;
 #[cfg(feature = "wayland")]
 use iced_runtime::command::platform_specific::wayland::Action as WaylandAction;
 #[cfg(feature = "wayland")]
 use iced_runtime::command::platform_specific::Action as PlatformAction;
 use iced_runtime::command::Action;
+#[cfg(feature = "wayland")]
+use sctk::reexports::protocols::xdg::shell::client::xdg_positioner::{Anchor, Gravity};
 use std::future::Future;
 
 /// Yields a command which contains a batch of commands.
@@ -31,79 +38,181 @@ pub fn message<M: Send + 'static>(message: M) -> Command<M> {
     future(async move { message })
 }
 
+/// Initiates a drag of the main window.
+pub fn drag<M>() -> Command<M> {
+    window_drag(window::Id(0))
+}
+
+/// Fullscreens the main window.
+pub fn fullscreen<M>() -> Command<M> {
+    window_fullscreen(window::Id(0))
+}
+
+/// Minimizes the main window.
+pub fn minimize<M>() -> Command<M> {
+    window_minimize(window::Id(0))
+}
+
+/// Sets the title of the main window.
+pub fn set_title<M>(title: String) -> Command<M> {
+    window_set_title(window::Id(0), title)
+}
+
+/// Sets the mode of the main window to windowed.
+pub fn set_windowed<M>() -> Command<M> {
+    window_set_windowed(window::Id(0))
+}
+
+/// Toggles the main window's maximization state.
+pub fn toggle_fullscreen<M>() -> Command<M> {
+    window_toggle_fullscreen(window::Id(0))
+}
+
+/// Opens a new window with the given settings.
+#[cfg(feature = "wayland")]
+pub fn window_open<M>(settings: SctkWindowSettings) -> Command<M> {
+    iced_sctk::commands::window::get_window(settings)
+}
+
+/// Closes a window.
+#[cfg(feature = "wayland")]
+pub fn window_close<M>(id: window::Id) -> Command<M> {
+    iced_sctk::commands::window::close_window(id)
+}
+
+/// Opens a context menu in a popup surface with the ID `id`, at the position in its parent
+/// window where the menu was requested.
+///
+/// The ID should be reserved with [`Core::next_surface_id`](crate::app::Core::next_surface_id),
+/// so that it does not collide with the IDs of windows.
+///
+/// The popup should show the items of the menu with [`crate::widget::menu::popup`].
+#[cfg(feature = "wayland")]
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn context_menu_popup<M>(
+    parent: window::Id,
+    id: window::Id,
+    popup: ContextMenuPopup,
+) -> Command<M> {
+    iced_sctk::commands::popup::get_popup(SctkPopupSettings {
+        parent,
+        id,
+        positioner: SctkPositioner {
+            size: Some((
+                popup.size.width.ceil() as u32,
+                popup.size.height.ceil() as u32,
+            )),
+            anchor_rect: iced::Rectangle {
+                x: popup.position.x as i32,
+                y: popup.position.y as i32,
+                width: 1,
+                height: 1,
+            },
+            anchor: Anchor::TopLeft,
+            gravity: Gravity::BottomRight,
+            reactive: true,
+            constraint_adjustment: 15, // slide_y, slide_x, flip_x, flip_y
+            ..Default::default()
+        },
+        parent_size: None,
+        grab: true,
+    })
+}
+
+/// Closes a popup surface, such as a context menu.
+#[cfg(feature = "wayland")]
+pub fn close_popup<M>(id: window::Id) -> Command<M> {
+    iced_sctk::commands::popup::destroy_popup(id)
+}
+
 /// Initiates a window drag.
 #[cfg(feature = "wayland")]
-pub fn drag<M>() -> Command<M> {
-    iced_sctk::commands::window::start_drag_window(window::Id(0))
+pub fn window_drag<M>(id: window::Id) -> Command<M> {
+    iced_sctk::commands::window::start_drag_window(id)
 }
 
 /// Initiates a window drag.
 #[cfg(not(feature = "wayland"))]
-pub fn drag<M>() -> Command<M> {
+#[allow(unused_variables)]
+pub fn window_drag<M>(id: window::Id) -> Command<M> {
     iced_runtime::window::drag()
 }
 
 /// Fullscreens the window.
 #[cfg(feature = "wayland")]
-pub fn fullscreen<M>() -> Command<M> {
-    iced_sctk::commands::window::set_mode_window(window::Id(0), Mode::Fullscreen)
+pub fn window_fullscreen<M>(id: window::Id) -> Command<M> {
+    iced_sctk::commands::window::set_mode_window(id, Mode::Fullscreen)
 }
 
 /// Fullscreens the window.
 #[cfg(not(feature = "wayland"))]
-pub fn fullscreen<M>() -> Command<M> {
+#[allow(unused_variables)]
+pub fn window_fullscreen<M>(id: window::Id) -> Command<M> {
     iced_runtime::window::change_mode(Mode::Fullscreen)
 }
 
+/// Maximizes the window.
+#[cfg(feature = "wayland")]
+pub fn window_maximize<M>(id: window::Id) -> Command<M> {
+    window_action(WindowAction::Maximize { id })
+}
+
+/// Maximizes the window.
+#[cfg(not(feature = "wayland"))]
+#[allow(unused_variables)]
+pub fn window_maximize<M>(id: window::Id) -> Command<M> {
+    iced_runtime::window::maximize(true)
+}
+
 /// Minimizes the window.
 #[cfg(feature = "wayland")]
-pub fn minimize<M>() -> Command<M> {
-    iced_sctk::commands::window::set_mode_window(window::Id(0), Mode::Hidden)
+pub fn window_minimize<M>(id: window::Id) -> Command<M> {
+    iced_sctk::commands::window::set_mode_window(id, Mode::Hidden)
 }
 
 /// Minimizes the window.
 #[cfg(not(feature = "wayland"))]
-pub fn minimize<M>() -> Command<M> {
+#[allow(unused_variables)]
+pub fn window_minimize<M>(id: window::Id) -> Command<M> {
     iced_runtime::window::minimize(true)
 }
 
 /// Sets the title of a window.
 #[cfg(feature = "wayland")]
-pub fn set_title<M>(title: String) -> Command<M> {
-    window_action(WindowAction::Title {
-        id: window::Id(0),
-        title,
-    })
+pub fn window_set_title<M>(id: window::Id, title: String) -> Command<M> {
+    window_action(WindowAction::Title { id, title })
 }
 
 /// Sets the title of a window.
 #[cfg(not(feature = "wayland"))]
 #[allow(unused_variables, clippy::needless_pass_by_value)]
-pub fn set_title<M>(title: String) -> Command<M> {
+pub fn window_set_title<M>(id: window::Id, title: String) -> Command<M> {
     Command::none()
 }
 
 /// Sets the window mode to windowed.
 #[cfg(feature = "wayland")]
-pub fn set_windowed<M>() -> Command<M> {
-    iced_sctk::commands::window::set_mode_window(window::Id(0), Mode::Windowed)
+pub fn window_set_windowed<M>(id: window::Id) -> Command<M> {
+    iced_sctk::commands::window::set_mode_window(id, Mode::Windowed)
 }
 
 /// Sets the window mode to windowed.
 #[cfg(not(feature = "wayland"))]
-pub fn set_windowed<M>() -> Command<M> {
+#[allow(unused_variables)]
+pub fn window_set_windowed<M>(id: window::Id) -> Command<M> {
     iced_runtime::window::change_mode(Mode::Windowed)
 }
 
 /// Toggles the windows' maximization state.
 #[cfg(feature = "wayland")]
-pub fn toggle_fullscreen<M>() -> Command<M> {
-    window_action(WindowAction::ToggleFullscreen { id: window::Id(0) })
+pub fn window_toggle_fullscreen<M>(id: window::Id) -> Command<M> {
+    window_action(WindowAction::ToggleFullscreen { id })
 }
 
 /// Toggles the windows' maximization state.
 #[cfg(not(feature = "wayland"))]
-pub fn toggle_fullscreen<M>() -> Command<M> {
+#[allow(unused_variables)]
+pub fn window_toggle_fullscreen<M>(id: window::Id) -> Command<M> {
     iced_runtime::window::toggle_maximize()
 }
 
@@ -3,12 +3,17 @@
 
 //! Select preferred fonts.
 
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::theme::THEME;
+use cosmic_theme::{FontWeight, TextStyle, Typography};
 pub use iced::Font;
 use iced::{
     font::{load, Error},
     Command,
 };
-use iced_core::font::Family;
+use iced_core::font::{Family, Weight};
 
 pub const FONT: Font = Font {
     family: Family::Name("Fira Sans"),
@@ -37,10 +42,83 @@ pub const FONT_SEMIBOLD: Font = Font {
 
 pub const FONT_SEMIBOLD_DATA: &[u8] = include_bytes!("../res/Fira/FiraSans-SemiBold.otf");
 
+pub const FONT_MONO: Font = Font {
+    family: Family::Name("Fira Mono"),
+    weight: iced_core::font::Weight::Normal,
+    stretch: iced_core::font::Stretch::Normal,
+    monospaced: true,
+};
+
+pub const FONT_MONO_DATA: &[u8] = include_bytes!("../res/Fira/FiraMono-Regular.otf");
+
 pub fn load_fonts() -> Command<Result<(), Error>> {
     Command::batch(vec![
         load(FONT_DATA),
         load(FONT_LIGHT_DATA),
         load(FONT_SEMIBOLD_DATA),
+        load(FONT_MONO_DATA),
     ])
 }
+
+lazy_static::lazy_static! {
+    /// Family names requested by themes, which iced requires to be static.
+    static ref FAMILIES: Mutex<HashMap<String, &'static str>> = Mutex::new(HashMap::new());
+}
+
+/// The font [`Family`] with the given name.
+///
+/// Each distinct family name is allocated once for the lifetime of the application.
+#[must_use]
+pub fn family(name: &str) -> Family {
+    let mut families = FAMILIES.lock().unwrap();
+    let name = *families
+        .entry(name.to_owned())
+        .or_insert_with(|| Box::leak(name.to_owned().into_boxed_str()));
+    Family::Name(name)
+}
+
+/// Converts a theme font weight into a font [`Weight`].
+#[must_use]
+pub fn weight(weight: FontWeight) -> Weight {
+    match weight {
+        FontWeight::Thin => Weight::Thin,
+        FontWeight::ExtraLight => Weight::ExtraLight,
+        FontWeight::Light => Weight::Light,
+        FontWeight::Normal => Weight::Normal,
+        FontWeight::Medium => Weight::Medium,
+        FontWeight::Semibold => Weight::Semibold,
+        FontWeight::Bold => Weight::Bold,
+        FontWeight::ExtraBold => Weight::ExtraBold,
+        FontWeight::Black => Weight::Black,
+    }
+}
+
+/// The interface [`Font`] for a text style of the theme's typography.
+#[must_use]
+pub fn from_text_style(typography: &Typography, style: &TextStyle) -> Font {
+    Font {
+        family: family(&typography.family),
+        weight: weight(style.weight),
+        ..FONT
+    }
+}
+
+/// The heading [`Font`] of the active COSMIC theme.
+#[must_use]
+pub fn heading() -> Font {
+    THEME.with(|t| {
+        let theme = t.borrow();
+        let typography = &theme.cosmic().typography;
+        from_text_style(typography, &typography.heading)
+    })
+}
+
+/// The monospaced [`Font`] of the theme's typography.
+#[must_use]
+pub fn monospace(typography: &Typography) -> Font {
+    Font {
+        family: family(&typography.monospace_family),
+        weight: weight(typography.monospace.weight),
+        ..FONT_MONO
+    }
+}
@@ -2,10 +2,15 @@
 // SPDX-License-Identifier: MPL-2.0
 
 //! Subscribe to common application keyboard shortcuts.
+//!
+//! The bindings of these shortcuts are declared by [`shortcuts`]. Applications which are run with
+//! [`crate::app::run`] keep them in their [`Core`](crate::app::Core), where the bindings remapped
+//! in the `shortcuts` key of the libcosmic config are applied.
 
+use crate::shortcuts::{Binding, Shortcuts};
 use iced::{
     event,
-    keyboard::{self, KeyCode},
+    keyboard::{self, KeyCode, Modifiers},
     mouse, subscription, Command, Event, Subscription,
 };
 use iced_core::{
@@ -23,6 +28,37 @@ pub enum Message {
     Search,
 }
 
+/// Keyboard navigation actions with their default bindings.
+#[must_use]
+pub fn shortcuts() -> Shortcuts<Message> {
+    Shortcuts::new()
+        .action("escape", Message::Escape, [Binding::key(KeyCode::Escape)])
+        .action(
+            "focus-next",
+            Message::FocusNext,
+            [Binding::key(KeyCode::Tab)],
+        )
+        .action(
+            "focus-previous",
+            Message::FocusPrevious,
+            [Binding::new(Modifiers::SHIFT, KeyCode::Tab)],
+        )
+        .action(
+            "fullscreen",
+            Message::Fullscreen,
+            [Binding::key(KeyCode::F11)],
+        )
+        .action(
+            "search",
+            Message::Search,
+            [Binding::new(Modifiers::CTRL, KeyCode::F)],
+        )
+}
+
+/// Emits the keyboard navigation actions of key presses which were not captured by a widget.
+///
+/// Only the default bindings are matched; applications run with [`crate::app::run`] receive
+/// these actions through their [`Core`](crate::app::Core) instead, with remapped bindings.
 pub fn subscription() -> Subscription<Message> {
     subscription::events_with(|event, status| {
         if event::Status::Ignored != status {
@@ -33,33 +69,11 @@ pub fn subscription() -> Subscription<Message> {
             Event::Keyboard(keyboard::Event::KeyPressed {
                 key_code,
                 modifiers,
-            }) => match key_code {
-                KeyCode::Tab => {
-                    return Some(if modifiers.shift() {
-                        Message::FocusPrevious
-                    } else {
-                        Message::FocusNext
-                    });
-                }
-
-                KeyCode::Escape => {
-                    return Some(Message::Escape);
-                }
-
-                KeyCode::F11 => {
-                    return Some(Message::Fullscreen);
-                }
-
-                KeyCode::F => {
-                    return if modifiers.control() {
-                        Some(Message::Search)
-                    } else {
-                        None
-                    };
-                }
-
-                _ => (),
-            },
+            }) => {
+                return shortcuts()
+                    .action_for(&Binding::new(modifiers, key_code))
+                    .copied();
+            }
 
             Event::Mouse(mouse::Event::ButtonPressed { .. }) => {
                 return Some(Message::Unfocus);
@@ -3,6 +3,8 @@
 
 #![allow(clippy::module_name_repetitions)]
 
+pub mod accessibility;
+
 pub mod app;
 pub use app::{Application, ApplicationExt};
 
@@ -41,6 +43,11 @@ pub mod keyboard_nav;
 #[cfg(feature = "wayland")]
 pub use sctk;
 
+pub mod shortcuts;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
 pub mod theme;
 pub use theme::Theme;
 
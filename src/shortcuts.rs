@@ -0,0 +1,745 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Named actions triggered by key bindings, which users may remap.
+//!
+//! ```ignore
+//! let shortcuts = Shortcuts::new()
+//!     .action("new-tab", Message::NewTab, ["Ctrl+T".parse().unwrap()])
+//!     .action("close-tab", Message::CloseTab, ["Ctrl+W".parse().unwrap()]);
+//! ```
+//!
+//! Bindings are remapped by storing a map of action names to bindings in the `shortcuts` key of
+//! a config, such as `{ "new-tab": ["Ctrl+N", "Ctrl+T"] }`, which is applied with
+//! [`Shortcuts::load`] and observed with [`remaps_subscription`].
+
+use cosmic_config::{
+    config_subscription, cosmic_config_derive::CosmicConfigEntry, Config, ConfigGet, ConfigSet,
+    CosmicConfigEntry,
+};
+use iced::keyboard::{self, KeyCode, Modifiers};
+use iced::{event, subscription, Event, Subscription};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Key of the remapped bindings in a config.
+pub const CONFIG_KEY: &str = "shortcuts";
+
+/// A key pressed while holding a set of modifiers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Binding {
+    pub modifiers: Modifiers,
+    pub key_code: KeyCode,
+}
+
+impl Binding {
+    /// A binding for a key pressed while holding `modifiers`.
+    #[must_use]
+    pub const fn new(modifiers: Modifiers, key_code: KeyCode) -> Self {
+        Self {
+            modifiers,
+            key_code,
+        }
+    }
+
+    /// A binding for a key pressed without any modifiers.
+    #[must_use]
+    pub const fn key(key_code: KeyCode) -> Self {
+        Self::new(Modifiers::empty(), key_code)
+    }
+}
+
+/// Names of modifiers, in the order they are displayed.
+const MODIFIERS: [(&str, Modifiers); 4] = [
+    ("Ctrl", Modifiers::CTRL),
+    ("Alt", Modifiers::ALT),
+    ("Shift", Modifiers::SHIFT),
+    ("Super", Modifiers::LOGO),
+];
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, modifier) in MODIFIERS {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        // every key is named in `KEYS`, so that bindings parse back from how they are displayed
+        match key_name(self.key_code) {
+            Some(name) => f.write_str(name),
+            None => write!(f, "{:?}", self.key_code),
+        }
+    }
+}
+
+/// Error parsing a [`Binding`].
+#[derive(Debug, thiserror::Error)]
+pub enum BindingError {
+    #[error("binding is missing a key")]
+    MissingKey,
+    #[error("unknown key `{0}`")]
+    UnknownKey(String),
+    #[error("unknown modifier `{0}`")]
+    UnknownModifier(String),
+}
+
+impl FromStr for Binding {
+    type Err = BindingError;
+
+    /// Parses bindings such as `Ctrl+Shift+T`, `F11` and `Super+Space`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // the plus key itself is written as `Ctrl++`
+        let (modifier_names, key) = match s.strip_suffix("++") {
+            Some(rest) => (rest, "+"),
+            None if s == "+" => ("", "+"),
+            None => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+
+        let key = key.trim();
+        if key.is_empty() {
+            return Err(BindingError::MissingKey);
+        }
+        let key_code = key_from_name(key).ok_or_else(|| BindingError::UnknownKey(key.into()))?;
+
+        let mut modifiers = Modifiers::empty();
+        for name in modifier_names.split('+').map(str::trim) {
+            modifiers |= match name.to_ascii_lowercase().as_str() {
+                "" if modifier_names.is_empty() => continue,
+                "ctrl" | "control" => Modifiers::CTRL,
+                "alt" => Modifiers::ALT,
+                "shift" => Modifiers::SHIFT,
+                "super" | "logo" | "meta" => Modifiers::LOGO,
+                _ => return Err(BindingError::UnknownModifier(name.into())),
+            };
+        }
+
+        Ok(Self::new(modifiers, key_code))
+    }
+}
+
+impl Serialize for Binding {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Binding {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let binding = Cow::<str>::deserialize(deserializer)?;
+        binding.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Bindings which users remapped, by the name of their action.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, CosmicConfigEntry)]
+pub struct Remaps {
+    /// Stored in the [`CONFIG_KEY`] of a config.
+    pub shortcuts: HashMap<String, Vec<Binding>>,
+}
+
+impl Remaps {
+    /// Version of the configs which remapped bindings are stored in.
+    #[must_use]
+    pub fn version() -> u64 {
+        1
+    }
+
+    /// Reads the bindings remapped in a config, which has none if the key was never written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config exists but could not be read.
+    pub fn load(config: &Config) -> Result<Self, cosmic_config::Error> {
+        match Self::get_entry(config) {
+            Ok(remaps) => Ok(remaps),
+            Err((mut errors, remaps)) => {
+                errors.retain(|err| !is_not_found(err));
+                errors.pop().map_or(Ok(remaps), Err)
+            }
+        }
+    }
+}
+
+fn is_not_found(err: &cosmic_config::Error) -> bool {
+    matches!(err, cosmic_config::Error::Io(err) if err.kind() == std::io::ErrorKind::NotFound)
+}
+
+/// A binding which triggers more than one action.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub binding: Binding,
+    pub actions: Vec<Cow<'static, str>>,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is bound to {}",
+            self.binding,
+            self.actions.join(", ")
+        )
+    }
+}
+
+/// An action, with the bindings which trigger it.
+#[derive(Clone, Debug)]
+struct Entry<A> {
+    name: Cow<'static, str>,
+    action: A,
+    defaults: Vec<Binding>,
+    bindings: Vec<Binding>,
+}
+
+/// A registry of named actions and the bindings which trigger them.
+#[derive(Clone, Debug)]
+pub struct Shortcuts<A> {
+    entries: Vec<Entry<A>>,
+}
+
+impl<A> Default for Shortcuts<A> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<A> Shortcuts<A> {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Declares an action, with the bindings which trigger it by default.
+    #[must_use]
+    pub fn action(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        action: A,
+        defaults: impl IntoIterator<Item = Binding>,
+    ) -> Self {
+        self.insert(name, action, defaults);
+        self
+    }
+
+    /// Declares an action, replacing any action of the same name.
+    pub fn insert(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+        action: A,
+        defaults: impl IntoIterator<Item = Binding>,
+    ) {
+        let name = name.into();
+        let defaults: Vec<Binding> = defaults.into_iter().collect();
+        let entry = Entry {
+            bindings: defaults.clone(),
+            name,
+            action,
+            defaults,
+        };
+
+        match self.entries.iter_mut().find(|e| e.name == entry.name) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+
+    /// The action triggered by a binding.
+    #[must_use]
+    pub fn action_for(&self, binding: &Binding) -> Option<&A> {
+        self.entries
+            .iter()
+            .find(|entry| entry.bindings.contains(binding))
+            .map(|entry| &entry.action)
+    }
+
+    /// The bindings which trigger an action.
+    #[must_use]
+    pub fn bindings(&self, name: &str) -> &[Binding] {
+        self.entry(name).map_or(&[], |entry| &entry.bindings)
+    }
+
+    /// The bindings which trigger an action unless the user has remapped it.
+    #[must_use]
+    pub fn default_bindings(&self, name: &str) -> &[Binding] {
+        self.entry(name).map_or(&[], |entry| &entry.defaults)
+    }
+
+    /// The first binding of an action, as it is displayed in menus.
+    #[must_use]
+    pub fn label(&self, name: &str) -> Option<String> {
+        self.bindings(name).first().map(Binding::to_string)
+    }
+
+    /// Replaces the bindings of an action, returning `false` if there is no such action.
+    pub fn set_bindings(
+        &mut self,
+        name: &str,
+        bindings: impl IntoIterator<Item = Binding>,
+    ) -> bool {
+        match self.entries.iter_mut().find(|entry| entry.name == name) {
+            Some(entry) => {
+                entry.bindings = bindings.into_iter().collect();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Restores the default bindings of every action.
+    pub fn reset(&mut self) {
+        for entry in &mut self.entries {
+            entry.bindings = entry.defaults.clone();
+        }
+    }
+
+    /// Iterates the name, action and bindings of each action, in the order they were declared.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &A, &[Binding])> {
+        self.entries.iter().map(|entry| {
+            (
+                entry.name.as_ref(),
+                &entry.action,
+                entry.bindings.as_slice(),
+            )
+        })
+    }
+
+    /// Restores the default bindings, then applies the bindings which the user remapped.
+    ///
+    /// Actions which were not remapped keep their default bindings.
+    pub fn remap(&mut self, remaps: &Remaps) {
+        self.reset();
+
+        for (name, bindings) in &remaps.shortcuts {
+            if !self.set_bindings(name, bindings.iter().copied()) {
+                tracing::warn!("ignoring bindings of unknown action `{}`", name);
+            }
+        }
+    }
+
+    /// Applies the bindings which the user remapped in the `shortcuts` key of a config.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config exists but could not be read.
+    pub fn load(&mut self, config: &Config) -> Result<(), cosmic_config::Error> {
+        self.remap(&Remaps::load(config)?);
+        Ok(())
+    }
+
+    /// Bindings which trigger more than one action.
+    #[must_use]
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        conflicts(self.names())
+    }
+
+    /// Bindings which trigger actions of both registries.
+    #[must_use]
+    pub fn conflicts_with<B>(&self, other: &Shortcuts<B>) -> Vec<Conflict> {
+        conflicts(self.names().chain(other.names()))
+            .into_iter()
+            .filter(|conflict| {
+                let ours = |name: &Cow<'static, str>| self.entry(name).is_some();
+                conflict.actions.iter().any(ours) && !conflict.actions.iter().all(ours)
+            })
+            .collect()
+    }
+
+    fn entry(&self, name: &str) -> Option<&Entry<A>> {
+        self.entries.iter().find(|entry| entry.name == name)
+    }
+
+    fn names(&self) -> impl Iterator<Item = (&Cow<'static, str>, &[Binding])> {
+        self.entries
+            .iter()
+            .map(|entry| (&entry.name, entry.bindings.as_slice()))
+    }
+}
+
+/// Groups the actions by binding, keeping the bindings of more than one action.
+fn conflicts<'a>(
+    names: impl Iterator<Item = (&'a Cow<'static, str>, &'a [Binding])>,
+) -> Vec<Conflict> {
+    let mut conflicts: Vec<Conflict> = Vec::new();
+
+    for (name, bindings) in names {
+        for binding in bindings {
+            match conflicts.iter_mut().find(|c| c.binding == *binding) {
+                Some(conflict) => conflict.actions.push(name.clone()),
+                None => conflicts.push(Conflict {
+                    binding: *binding,
+                    actions: vec![name.clone()],
+                }),
+            }
+        }
+    }
+
+    conflicts.retain(|conflict| conflict.actions.len() > 1);
+    conflicts
+}
+
+/// Emits the bindings of key presses which were not captured by a widget.
+pub fn subscription() -> Subscription<Binding> {
+    subscription::events_with(|event, status| {
+        if event::Status::Ignored != status {
+            return None;
+        }
+
+        match event {
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                modifiers,
+            }) => Some(Binding::new(modifiers, key_code)),
+            _ => None,
+        }
+    })
+}
+
+/// Observe the bindings which users remap in the config of `config_id`.
+///
+/// Each subscription needs a distinct `id`.
+pub fn remaps_subscription(
+    id: u64,
+    config_id: impl Into<Cow<'static, str>>,
+    version: u64,
+) -> Subscription<Remaps> {
+    config_subscription::<u64, Remaps>(id, config_id.into(), version).map(|(_, res)| {
+        res.unwrap_or_else(|(errors, remaps)| {
+            for err in errors.iter().filter(|err| !is_not_found(err)) {
+                tracing::error!("{:?}", err);
+            }
+            remaps
+        })
+    })
+}
+
+/// Names of keys, as they are written in bindings.
+const KEYS: &[(&str, KeyCode)] = &[
+    ("A", KeyCode::A),
+    ("B", KeyCode::B),
+    ("C", KeyCode::C),
+    ("D", KeyCode::D),
+    ("E", KeyCode::E),
+    ("F", KeyCode::F),
+    ("G", KeyCode::G),
+    ("H", KeyCode::H),
+    ("I", KeyCode::I),
+    ("J", KeyCode::J),
+    ("K", KeyCode::K),
+    ("L", KeyCode::L),
+    ("M", KeyCode::M),
+    ("N", KeyCode::N),
+    ("O", KeyCode::O),
+    ("P", KeyCode::P),
+    ("Q", KeyCode::Q),
+    ("R", KeyCode::R),
+    ("S", KeyCode::S),
+    ("T", KeyCode::T),
+    ("U", KeyCode::U),
+    ("V", KeyCode::V),
+    ("W", KeyCode::W),
+    ("X", KeyCode::X),
+    ("Y", KeyCode::Y),
+    ("Z", KeyCode::Z),
+    ("0", KeyCode::Key0),
+    ("1", KeyCode::Key1),
+    ("2", KeyCode::Key2),
+    ("3", KeyCode::Key3),
+    ("4", KeyCode::Key4),
+    ("5", KeyCode::Key5),
+    ("6", KeyCode::Key6),
+    ("7", KeyCode::Key7),
+    ("8", KeyCode::Key8),
+    ("9", KeyCode::Key9),
+    ("F1", KeyCode::F1),
+    ("F2", KeyCode::F2),
+    ("F3", KeyCode::F3),
+    ("F4", KeyCode::F4),
+    ("F5", KeyCode::F5),
+    ("F6", KeyCode::F6),
+    ("F7", KeyCode::F7),
+    ("F8", KeyCode::F8),
+    ("F9", KeyCode::F9),
+    ("F10", KeyCode::F10),
+    ("F11", KeyCode::F11),
+    ("F12", KeyCode::F12),
+    ("F13", KeyCode::F13),
+    ("F14", KeyCode::F14),
+    ("F15", KeyCode::F15),
+    ("F16", KeyCode::F16),
+    ("F17", KeyCode::F17),
+    ("F18", KeyCode::F18),
+    ("F19", KeyCode::F19),
+    ("F20", KeyCode::F20),
+    ("F21", KeyCode::F21),
+    ("F22", KeyCode::F22),
+    ("F23", KeyCode::F23),
+    ("F24", KeyCode::F24),
+    ("Escape", KeyCode::Escape),
+    ("Tab", KeyCode::Tab),
+    ("Space", KeyCode::Space),
+    ("Enter", KeyCode::Enter),
+    ("Backspace", KeyCode::Backspace),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Print", KeyCode::Snapshot),
+    ("ScrollLock", KeyCode::Scroll),
+    ("Pause", KeyCode::Pause),
+    ("CapsLock", KeyCode::Capital),
+    ("NumLock", KeyCode::Numlock),
+    ("Compose", KeyCode::Compose),
+    ("Numpad0", KeyCode::Numpad0),
+    ("Numpad1", KeyCode::Numpad1),
+    ("Numpad2", KeyCode::Numpad2),
+    ("Numpad3", KeyCode::Numpad3),
+    ("Numpad4", KeyCode::Numpad4),
+    ("Numpad5", KeyCode::Numpad5),
+    ("Numpad6", KeyCode::Numpad6),
+    ("Numpad7", KeyCode::Numpad7),
+    ("Numpad8", KeyCode::Numpad8),
+    ("Numpad9", KeyCode::Numpad9),
+    ("NumpadAdd", KeyCode::NumpadAdd),
+    ("NumpadSubtract", KeyCode::NumpadSubtract),
+    ("NumpadMultiply", KeyCode::NumpadMultiply),
+    ("NumpadDivide", KeyCode::NumpadDivide),
+    ("NumpadDecimal", KeyCode::NumpadDecimal),
+    ("NumpadComma", KeyCode::NumpadComma),
+    ("NumpadEnter", KeyCode::NumpadEnter),
+    ("NumpadEquals", KeyCode::NumpadEquals),
+    ("LCtrl", KeyCode::LControl),
+    ("RCtrl", KeyCode::RControl),
+    ("LAlt", KeyCode::LAlt),
+    ("RAlt", KeyCode::RAlt),
+    ("LShift", KeyCode::LShift),
+    ("RShift", KeyCode::RShift),
+    ("LSuper", KeyCode::LWin),
+    ("RSuper", KeyCode::RWin),
+    ("Mute", KeyCode::Mute),
+    ("VolumeDown", KeyCode::VolumeDown),
+    ("VolumeUp", KeyCode::VolumeUp),
+    ("PlayPause", KeyCode::PlayPause),
+    ("MediaStop", KeyCode::MediaStop),
+    ("MediaSelect", KeyCode::MediaSelect),
+    ("NextTrack", KeyCode::NextTrack),
+    ("PrevTrack", KeyCode::PrevTrack),
+    ("Calculator", KeyCode::Calculator),
+    ("Mail", KeyCode::Mail),
+    ("MyComputer", KeyCode::MyComputer),
+    ("Copy", KeyCode::Copy),
+    ("Cut", KeyCode::Cut),
+    ("Paste", KeyCode::Paste),
+    ("Stop", KeyCode::Stop),
+    ("Power", KeyCode::Power),
+    ("Sleep", KeyCode::Sleep),
+    ("Wake", KeyCode::Wake),
+    ("SysRq", KeyCode::Sysrq),
+    ("NavigateBackward", KeyCode::NavigateBackward),
+    ("NavigateForward", KeyCode::NavigateForward),
+    ("WebBack", KeyCode::WebBack),
+    ("WebForward", KeyCode::WebForward),
+    ("WebFavorites", KeyCode::WebFavorites),
+    ("WebHome", KeyCode::WebHome),
+    ("WebRefresh", KeyCode::WebRefresh),
+    ("WebSearch", KeyCode::WebSearch),
+    ("WebStop", KeyCode::WebStop),
+    ("Kana", KeyCode::Kana),
+    ("Kanji", KeyCode::Kanji),
+    ("Convert", KeyCode::Convert),
+    ("NoConvert", KeyCode::NoConvert),
+    ("Yen", KeyCode::Yen),
+    ("AbntC1", KeyCode::AbntC1),
+    ("AbntC2", KeyCode::AbntC2),
+    ("Ax", KeyCode::Ax),
+    ("OEM102", KeyCode::OEM102),
+    ("Unlabeled", KeyCode::Unlabeled),
+    ("Menu", KeyCode::Apps),
+    ("+", KeyCode::Plus),
+    ("-", KeyCode::Minus),
+    ("=", KeyCode::Equals),
+    (",", KeyCode::Comma),
+    (".", KeyCode::Period),
+    ("/", KeyCode::Slash),
+    ("\\", KeyCode::Backslash),
+    (";", KeyCode::Semicolon),
+    ("'", KeyCode::Apostrophe),
+    ("[", KeyCode::LBracket),
+    ("]", KeyCode::RBracket),
+    ("`", KeyCode::Grave),
+    ("*", KeyCode::Asterisk),
+    ("@", KeyCode::At),
+    (":", KeyCode::Colon),
+    ("^", KeyCode::Caret),
+    ("_", KeyCode::Underline),
+];
+
+pub(crate) fn key_name(key_code: KeyCode) -> Option<&'static str> {
+    KEYS.iter()
+        .find(|(_, code)| *code == key_code)
+        .map(|(name, _)| *name)
+}
+
+fn key_from_name(name: &str) -> Option<KeyCode> {
+    KEYS.iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, code)| *code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Binding, Conflict, Remaps, Shortcuts, CONFIG_KEY, KEYS};
+    use cosmic_config::{Config, ConfigSet};
+    use iced::keyboard::{KeyCode, Modifiers};
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Action {
+        Copy,
+        Find,
+        ZoomIn,
+    }
+
+    fn shortcuts() -> Shortcuts<Action> {
+        Shortcuts::new()
+            .action(
+                "copy",
+                Action::Copy,
+                [Binding::new(Modifiers::CTRL, KeyCode::C)],
+            )
+            .action(
+                "find",
+                Action::Find,
+                [Binding::new(Modifiers::CTRL, KeyCode::F)],
+            )
+            .action(
+                "zoom-in",
+                Action::ZoomIn,
+                [Binding::new(Modifiers::CTRL, KeyCode::Plus)],
+            )
+    }
+
+    fn config(test_name: &str) -> (Config, std::path::PathBuf) {
+        let path = std::env::temp_dir().join(format!(
+            "libcosmic-test-{}-{}",
+            std::process::id(),
+            test_name
+        ));
+        let config =
+            Config::with_custom_path("com.system76.Test", Remaps::version(), path.clone()).unwrap();
+        (config, path)
+    }
+
+    #[test]
+    fn test_parse() {
+        let plus = Binding::new(Modifiers::CTRL, KeyCode::Plus);
+        assert_eq!("Ctrl++".parse::<Binding>().unwrap(), plus);
+        assert_eq!(
+            "Shift+Tab".parse::<Binding>().unwrap(),
+            Binding::new(Modifiers::SHIFT, KeyCode::Tab)
+        );
+        assert!("Ctrl+".parse::<Binding>().is_err());
+        assert!("Hyper+A".parse::<Binding>().is_err());
+        assert!("Ctrl+Nope".parse::<Binding>().is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for binding in [
+            "Ctrl++",
+            "Shift+Tab",
+            "Ctrl+Alt+Shift+Super+F24",
+            "NumpadAdd",
+        ] {
+            assert_eq!(binding.parse::<Binding>().unwrap().to_string(), binding);
+        }
+
+        for &(_, key_code) in KEYS {
+            let binding = Binding::new(Modifiers::CTRL | Modifiers::SHIFT, key_code);
+            assert_eq!(binding.to_string().parse::<Binding>().unwrap(), binding);
+        }
+    }
+
+    #[test]
+    fn test_conflicts() {
+        let mut shortcuts = shortcuts();
+        assert!(shortcuts.conflicts().is_empty());
+
+        let binding = Binding::new(Modifiers::CTRL, KeyCode::C);
+        shortcuts.set_bindings("find", [binding]);
+        assert_eq!(
+            shortcuts.conflicts(),
+            vec![Conflict {
+                binding,
+                actions: vec!["copy".into(), "find".into()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_conflicts_with() {
+        let shortcuts = shortcuts();
+        let mut other = Shortcuts::new()
+            .action("search", (), [Binding::new(Modifiers::CTRL, KeyCode::F)])
+            .action("tab", (), [Binding::key(KeyCode::Tab)])
+            .action("indent", (), [Binding::key(KeyCode::Tab)]);
+
+        // conflicts within the other registry are not reported
+        let conflicts = shortcuts.conflicts_with(&other);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].actions, vec!["find", "search"]);
+
+        other.set_bindings("search", []);
+        assert!(shortcuts.conflicts_with(&other).is_empty());
+    }
+
+    #[test]
+    fn test_load() {
+        let (config, path) = config("shortcuts-load");
+        let mut shortcuts = shortcuts();
+
+        // nothing was remapped
+        shortcuts.load(&config).unwrap();
+        assert_eq!(
+            shortcuts.bindings("find"),
+            shortcuts.default_bindings("find")
+        );
+
+        let remapped = [
+            Binding::new(Modifiers::CTRL, KeyCode::K),
+            Binding::key(KeyCode::F3),
+        ];
+        let mut remaps = Remaps::default();
+        remaps.shortcuts.insert("find".into(), remapped.to_vec());
+        remaps.shortcuts.insert("unknown".into(), remapped.to_vec());
+        config.set(CONFIG_KEY, &remaps.shortcuts).unwrap();
+
+        shortcuts.load(&config).unwrap();
+        assert_eq!(shortcuts.bindings("find"), remapped);
+        assert_eq!(shortcuts.action_for(&remapped[1]), Some(&Action::Find));
+        assert_eq!(
+            shortcuts.action_for(&Binding::new(Modifiers::CTRL, KeyCode::F)),
+            None
+        );
+
+        // actions which are no longer remapped return to their defaults
+        config.set(CONFIG_KEY, Remaps::default().shortcuts).unwrap();
+        shortcuts.load(&config).unwrap();
+        let _ = std::fs::remove_dir_all(path);
+        assert_eq!(
+            shortcuts.bindings("find"),
+            shortcuts.default_bindings("find")
+        );
+    }
+}
@@ -0,0 +1,667 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Test applications without a compositor or GPU.
+//!
+//! A [`Harness`] runs an [`Application`] with a [`Core`], renders its main window offscreen
+//! with the tiny-skia renderer, and feeds it events and messages:
+//!
+//! ```ignore
+//! #[test]
+//! fn delete_asks_for_confirmation() {
+//!     let mut harness = Harness::<App>::new(Core::default(), (), (800, 600));
+//!     harness.update(Message::App(AppMessage::Delete));
+//!     harness.drain_commands();
+//!
+//!     testing::assert_golden(&harness.render(), "tests/golden/delete-dialog.png");
+//!
+//!     harness.press_key(KeyCode::Escape, Modifiers::empty());
+//!     assert!(harness.app().dialog().is_none());
+//! }
+//! ```
+//!
+//! Golden images are written instead of compared when the `COSMIC_UPDATE_GOLDEN` environment
+//! variable is set, which is also how they are created for new tests.
+
+use crate::app::{cosmic, Application, ApplicationExt, Core, Message, Settings};
+use crate::shortcuts::Binding;
+use crate::{font, Renderer};
+use iced::Application as IcedApplication;
+use iced_core::keyboard::{self, KeyCode, Modifiers};
+use iced_core::{clipboard, event, mouse, renderer, Color, Event, Point, Rectangle, Size};
+use iced_runtime::command::Action;
+use iced_runtime::user_interface::{self, UserInterface};
+use iced_style::application::StyleSheet as _;
+use iced_tiny_skia::graphics::Viewport;
+use std::borrow::Cow;
+use std::path::Path;
+
+/// Environment variable which overwrites golden images with the rendered images.
+pub const UPDATE_GOLDEN: &str = "COSMIC_UPDATE_GOLDEN";
+
+/// Maximum difference of a color channel for pixels of golden images to be considered equal.
+pub const GOLDEN_TOLERANCE: u8 = 2;
+
+/// Limits how many rounds of commands are run by [`Harness::drain_commands`], in case
+/// commands keep producing messages which produce more commands.
+const MAX_COMMAND_ROUNDS: usize = 64;
+
+/// Runs an application headlessly, rendering its main window offscreen.
+pub struct Harness<App: Application> {
+    cosmic: cosmic::Cosmic<App>,
+    renderer: Renderer,
+    cache: user_interface::Cache,
+    clipboard: clipboard::Null,
+    commands: Vec<iced::Command<Message<App::Message>>>,
+    cursor: mouse::Cursor,
+    modifiers: Modifiers,
+    /// Physical size of the main window.
+    size: (u32, u32),
+}
+
+impl<App: Application> Harness<App> {
+    /// Initializes the application with a main window of `size`, in physical pixels.
+    ///
+    /// The commands returned by [`Application::init`] are queued, and run by
+    /// [`Harness::drain_commands`].
+    pub fn new(mut core: Core, flags: App::Flags, size: (u32, u32)) -> Self {
+        let settings = Settings::default();
+        core.set_window_width(size.0);
+        core.set_window_height(size.1);
+
+        let backend = iced_tiny_skia::Backend::new(iced_tiny_skia::Settings {
+            default_font: settings.default_font,
+            default_text_size: settings.default_text_size,
+        });
+        let mut renderer = Renderer::TinySkia(iced_tiny_skia::Renderer::new(backend));
+
+        // the bundled fonts keep rendering the same regardless of the fonts of the system
+        for data in [
+            font::FONT_DATA,
+            font::FONT_LIGHT_DATA,
+            font::FONT_SEMIBOLD_DATA,
+            font::FONT_MONO_DATA,
+        ] {
+            iced_core::text::Renderer::load_font(&mut renderer, Cow::Borrowed(data));
+        }
+
+        let (cosmic, command) = <cosmic::Cosmic<App> as IcedApplication>::new((core, flags));
+
+        Self {
+            cosmic,
+            renderer,
+            cache: user_interface::Cache::default(),
+            clipboard: clipboard::Null,
+            commands: vec![command],
+            cursor: mouse::Cursor::Unavailable,
+            modifiers: Modifiers::empty(),
+            size,
+        }
+    }
+
+    /// The application under test.
+    pub fn app(&self) -> &App {
+        &self.cosmic.app
+    }
+
+    /// The application under test, such as for setting up its state.
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.cosmic.app
+    }
+
+    /// Handles a message, queueing the command which it returns.
+    pub fn update(&mut self, message: Message<App::Message>) {
+        let command = IcedApplication::update(&mut self.cosmic, message);
+        self.commands.push(command);
+    }
+
+    /// Resizes the main window, in physical pixels.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.size = (width, height);
+        self.update(Message::Cosmic(cosmic::Message::WindowResize(
+            iced::window::Id(0),
+            width,
+            height,
+        )));
+    }
+
+    /// Sends events to the widgets of the main window, and handles the messages which they
+    /// produce.
+    ///
+    /// Key presses which no widget captures trigger the shortcuts of the application and of
+    /// libcosmic, as they would in a running application.
+    pub fn events(&mut self, events: &[Event]) {
+        for event in events {
+            match event {
+                Event::Mouse(mouse::Event::CursorMoved { position }) => {
+                    self.cursor = mouse::Cursor::Available(*position);
+                }
+                Event::Mouse(mouse::Event::CursorLeft) => {
+                    self.cursor = mouse::Cursor::Unavailable;
+                }
+                Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                    self.modifiers = *modifiers;
+                }
+                _ => (),
+            }
+        }
+
+        let mut messages = Vec::new();
+        let size = self.logical_size();
+        let cache = std::mem::take(&mut self.cache);
+        let mut interface =
+            UserInterface::build(self.cosmic.app.view_main(), size, cache, &mut self.renderer);
+
+        let (_state, statuses) = interface.update(
+            events,
+            self.cursor,
+            &mut self.renderer,
+            &mut self.clipboard,
+            &mut messages,
+        );
+
+        self.cache = interface.into_cache();
+
+        for (event, status) in events.iter().zip(statuses) {
+            if let (
+                Event::Keyboard(keyboard::Event::KeyPressed {
+                    key_code,
+                    modifiers,
+                }),
+                event::Status::Ignored,
+            ) = (event, status)
+            {
+                let binding = Binding::new(*modifiers, *key_code);
+                messages.push(Message::Cosmic(cosmic::Message::Shortcut(binding)));
+            }
+        }
+
+        for message in messages {
+            self.update(message);
+        }
+    }
+
+    /// Sends one event to the widgets of the main window.
+    pub fn event(&mut self, event: Event) {
+        self.events(&[event]);
+    }
+
+    /// Moves the pointer to a position in the main window, in logical pixels.
+    pub fn move_cursor(&mut self, position: Point) {
+        self.event(Event::Mouse(mouse::Event::CursorMoved { position }));
+    }
+
+    /// Clicks a mouse button at a position in the main window, in logical pixels.
+    pub fn click(&mut self, position: Point, button: mouse::Button) {
+        self.move_cursor(position);
+        self.event(Event::Mouse(mouse::Event::ButtonPressed(button)));
+        self.event(Event::Mouse(mouse::Event::ButtonReleased(button)));
+    }
+
+    /// Presses and releases a key while holding `modifiers`.
+    pub fn press_key(&mut self, key_code: KeyCode, modifiers: Modifiers) {
+        let previous = self.modifiers;
+        if modifiers != previous {
+            self.event(Event::Keyboard(keyboard::Event::ModifiersChanged(
+                modifiers,
+            )));
+        }
+
+        self.event(Event::Keyboard(keyboard::Event::KeyPressed {
+            key_code,
+            modifiers,
+        }));
+        self.event(Event::Keyboard(keyboard::Event::KeyReleased {
+            key_code,
+            modifiers,
+        }));
+
+        if modifiers != previous {
+            self.event(Event::Keyboard(keyboard::Event::ModifiersChanged(previous)));
+        }
+    }
+
+    /// Types text into the focused widget.
+    pub fn type_text(&mut self, text: &str) {
+        let events: Vec<Event> = text
+            .chars()
+            .map(|c| Event::Keyboard(keyboard::Event::CharacterReceived(c)))
+            .collect();
+
+        self.events(&events);
+    }
+
+    /// Runs the queued commands until no more are produced, returning the actions which can not
+    /// be performed headlessly, such as those of windows and the clipboard.
+    ///
+    /// Futures are run to completion on the current thread with
+    /// [`block_on`](iced::futures::executor::block_on), and the messages which they yield are
+    /// handled in turn. Streams are dropped.
+    ///
+    /// # Panics
+    ///
+    /// Futures which need a runtime such as tokio, for its timers or I/O, panic because none is
+    /// running on the current thread. Their commands should be replaced by the messages which
+    /// they would yield in tests.
+    pub fn drain_commands(&mut self) -> Vec<Action<Message<App::Message>>> {
+        let mut unhandled = Vec::new();
+
+        for _ in 0..MAX_COMMAND_ROUNDS {
+            let commands = std::mem::take(&mut self.commands);
+            if commands.is_empty() {
+                break;
+            }
+
+            for action in commands.into_iter().flat_map(iced::Command::actions) {
+                match action {
+                    Action::Future(future) => {
+                        let message = iced::futures::executor::block_on(future);
+                        self.update(message);
+                    }
+
+                    Action::LoadFont { bytes, tagger } => {
+                        iced_core::text::Renderer::load_font(&mut self.renderer, bytes);
+                        self.update(tagger(Ok(())));
+                    }
+
+                    Action::Stream(_) => (),
+
+                    action => unhandled.push(action),
+                }
+            }
+        }
+
+        unhandled
+    }
+
+    /// Renders the main window.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn render(&mut self) -> Snapshot {
+        let (width, height) = self.size;
+        let theme = self.cosmic.theme();
+        let appearance = theme.appearance(&self.cosmic.style());
+        let size = self.logical_size();
+
+        let cache = std::mem::take(&mut self.cache);
+        let mut interface =
+            UserInterface::build(self.cosmic.app.view_main(), size, cache, &mut self.renderer);
+
+        let _interaction = interface.draw(
+            &mut self.renderer,
+            &theme,
+            &renderer::Style {
+                text_color: appearance.text_color,
+            },
+            self.cursor,
+        );
+
+        self.cache = interface.into_cache();
+
+        let mut pixmap = tiny_skia::Pixmap::new(width.max(1), height.max(1))
+            .expect("window size exceeds the limits of tiny-skia");
+        let mut mask = tiny_skia::Mask::new(width.max(1), height.max(1))
+            .expect("window size exceeds the limits of tiny-skia");
+        let viewport = Viewport::with_physical_size(
+            Size::new(width, height),
+            f64::from(self.cosmic.app.core().scale_factor()),
+        );
+        let damage = [Rectangle::with_size(Size::new(width as f32, height as f32))];
+
+        #[allow(irrefutable_let_patterns)]
+        let Renderer::TinySkia(renderer) = &mut self.renderer
+        else {
+            unreachable!("the harness always renders with tiny-skia");
+        };
+
+        renderer.with_primitives(|backend, primitives| {
+            backend.draw::<&str>(
+                &mut pixmap.as_mut(),
+                &mut mask,
+                primitives,
+                &viewport,
+                &damage,
+                appearance.background_color,
+                &[],
+            );
+        });
+
+        Snapshot {
+            width,
+            height,
+            pixels: pixmap
+                .pixels()
+                .iter()
+                .flat_map(|pixel| {
+                    let color = pixel.demultiply();
+                    [color.red(), color.green(), color.blue(), color.alpha()]
+                })
+                .collect(),
+        }
+    }
+
+    /// Size of the main window in logical pixels, in which widgets are laid out.
+    #[allow(clippy::cast_precision_loss)]
+    fn logical_size(&self) -> Size {
+        let scale_factor = self.cosmic.app.core().scale_factor();
+        Size::new(
+            self.size.0 as f32 / scale_factor,
+            self.size.1 as f32 / scale_factor,
+        )
+    }
+}
+
+/// An image of a rendered window.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Width in physical pixels.
+    pub width: u32,
+    /// Height in physical pixels.
+    pub height: u32,
+    /// Pixels in rows from the top, as RGBA without premultiplied alpha.
+    pub pixels: Vec<u8>,
+}
+
+impl Snapshot {
+    /// The color of a pixel, if it is within the image.
+    #[must_use]
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let index = (y as usize * self.width as usize + x as usize) * 4;
+        let &[r, g, b, a] = &self.pixels[index..index + 4] else {
+            return None;
+        };
+
+        Some(Color::from_rgba8(r, g, b, f32::from(a) / 255.0))
+    }
+
+    /// The number of pixels which differ from another image by more than `tolerance` in any
+    /// channel, or `None` if the images differ in size.
+    #[must_use]
+    pub fn mismatched_pixels(&self, other: &Self, tolerance: u8) -> Option<usize> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return None;
+        }
+
+        let mismatched = self
+            .pixels
+            .chunks_exact(4)
+            .zip(other.pixels.chunks_exact(4))
+            .filter(|(a, b)| a.iter().zip(*b).any(|(a, b)| a.abs_diff(*b) > tolerance))
+            .count();
+
+        Some(mismatched)
+    }
+
+    /// Loads an image from a PNG file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be read or decoded.
+    pub fn load_png(path: impl AsRef<Path>) -> image::ImageResult<Self> {
+        let image = image::open(path)?.into_rgba8();
+
+        Ok(Self {
+            width: image.width(),
+            height: image.height(),
+            pixels: image.into_raw(),
+        })
+    }
+
+    /// Saves the image as a PNG file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file could not be encoded or written.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> image::ImageResult<()> {
+        image::save_buffer(
+            path,
+            &self.pixels,
+            self.width,
+            self.height,
+            image::ColorType::Rgba8,
+        )
+    }
+}
+
+/// Compares a rendered image to the golden image at `path`.
+///
+/// The golden image is written instead if [`UPDATE_GOLDEN`] is set.
+///
+/// # Panics
+///
+/// Panics if the images differ, or if the golden image does not exist, after writing the
+/// rendered image beside the golden image with the extension `actual.png` for inspection.
+pub fn assert_golden(snapshot: &Snapshot, path: impl AsRef<Path>) {
+    let path = path.as_ref();
+
+    if std::env::var_os(UPDATE_GOLDEN).is_some() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("failed to create golden image directory");
+        }
+
+        snapshot
+            .save_png(path)
+            .unwrap_or_else(|why| panic!("failed to write {}: {why}", path.display()));
+        return;
+    }
+
+    let actual = path.with_extension("actual.png");
+
+    // a missing golden image would otherwise let a test pass without comparing anything, such
+    // as when the image was not committed
+    if !path.exists() {
+        let _res = snapshot.save_png(&actual);
+        panic!(
+            "{} does not exist; set {UPDATE_GOLDEN} to write it, or see {}",
+            path.display(),
+            actual.display()
+        );
+    }
+
+    let golden = Snapshot::load_png(path)
+        .unwrap_or_else(|why| panic!("failed to read {}: {why}", path.display()));
+
+    let mismatched = snapshot.mismatched_pixels(&golden, GOLDEN_TOLERANCE);
+    if mismatched == Some(0) {
+        return;
+    }
+
+    let _res = snapshot.save_png(&actual);
+
+    match mismatched {
+        Some(count) => panic!(
+            "{count} pixels differ from {}; see {}",
+            path.display(),
+            actual.display()
+        ),
+        None => panic!(
+            "rendered {}x{} instead of {}x{} of {}; see {}",
+            snapshot.width,
+            snapshot.height,
+            golden.width,
+            golden.height,
+            path.display(),
+            actual.display()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{assert_golden, Harness, Snapshot, UPDATE_GOLDEN};
+    use crate::app::{Command, Core, Message};
+    use crate::shortcuts::{Binding, Shortcuts};
+    use crate::{executor, Element};
+    use iced::widget::{button, text};
+    use iced::Length;
+    use iced_core::keyboard::{KeyCode, Modifiers};
+    use iced_core::{mouse, Point};
+    use std::panic;
+
+    struct App {
+        core: Core,
+        loaded: Option<u32>,
+        presses: usize,
+        shortcuts: Shortcuts<AppMessage>,
+    }
+
+    #[derive(Clone, Debug)]
+    enum AppMessage {
+        Loaded(u32),
+        Pressed,
+    }
+
+    impl crate::Application for App {
+        type Executor = executor::Default;
+        type Flags = ();
+        type Message = AppMessage;
+
+        const APP_ID: &'static str = "com.system76.TestApp";
+
+        fn core(&self) -> &Core {
+            &self.core
+        }
+
+        fn core_mut(&mut self) -> &mut Core {
+            &mut self.core
+        }
+
+        fn init(core: Core, _flags: Self::Flags) -> (Self, Command<Self::Message>) {
+            let app = App {
+                core,
+                loaded: None,
+                presses: 0,
+                shortcuts: Shortcuts::new().action(
+                    "press",
+                    AppMessage::Pressed,
+                    [Binding::new(Modifiers::CTRL, KeyCode::P)],
+                ),
+            };
+
+            let command =
+                iced::Command::perform(async { 42 }, |n| Message::App(AppMessage::Loaded(n)));
+
+            (app, command)
+        }
+
+        fn shortcuts(&self) -> Option<&Shortcuts<Self::Message>> {
+            Some(&self.shortcuts)
+        }
+
+        fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
+            match message {
+                AppMessage::Loaded(n) => self.loaded = Some(n),
+                AppMessage::Pressed => self.presses += 1,
+            }
+
+            Command::none()
+        }
+
+        fn view(&self) -> Element<Self::Message> {
+            button(text("Press"))
+                .on_press(AppMessage::Pressed)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        }
+    }
+
+    fn harness() -> Harness<App> {
+        let mut core = Core::default();
+        core.window.show_headerbar = false;
+        Harness::new(core, (), (400, 300))
+    }
+
+    #[test]
+    fn test_drain_commands() {
+        let mut harness = harness();
+        assert_eq!(harness.app().loaded, None);
+
+        assert!(harness.drain_commands().is_empty());
+        assert_eq!(harness.app().loaded, Some(42));
+    }
+
+    #[test]
+    fn test_click() {
+        let mut harness = harness();
+        harness.click(Point::new(200.0, 150.0), mouse::Button::Left);
+        assert_eq!(harness.app().presses, 1);
+
+        let snapshot = harness.render();
+        assert_eq!((snapshot.width, snapshot.height), (400, 300));
+        assert!(snapshot.pixel(200, 150).is_some());
+        assert!(snapshot.pixel(400, 150).is_none());
+    }
+
+    #[test]
+    fn test_press_key() {
+        let mut harness = harness();
+        harness.press_key(KeyCode::P, Modifiers::empty());
+        assert_eq!(harness.app().presses, 0);
+
+        harness.press_key(KeyCode::P, Modifiers::CTRL);
+        assert_eq!(harness.app().presses, 1);
+    }
+
+    fn snapshot(pixels: &[[u8; 4]]) -> Snapshot {
+        Snapshot {
+            width: u32::try_from(pixels.len()).unwrap(),
+            height: 1,
+            pixels: pixels.concat(),
+        }
+    }
+
+    #[test]
+    fn test_mismatched_pixels() {
+        let a = snapshot(&[[0, 0, 0, 255], [10, 20, 30, 255], [255, 255, 255, 0]]);
+        assert_eq!(a.mismatched_pixels(&a, 0), Some(0));
+
+        let b = snapshot(&[[2, 0, 0, 255], [10, 23, 30, 255], [255, 255, 255, 0]]);
+        assert_eq!(a.mismatched_pixels(&b, 0), Some(2));
+        assert_eq!(a.mismatched_pixels(&b, 2), Some(1));
+        assert_eq!(a.mismatched_pixels(&b, 3), Some(0));
+
+        // images of different sizes are not compared
+        let c = snapshot(&[[0, 0, 0, 255]]);
+        assert_eq!(a.mismatched_pixels(&c, 255), None);
+    }
+
+    #[test]
+    fn test_golden() {
+        // the images would be written instead of compared
+        if std::env::var_os(UPDATE_GOLDEN).is_some() {
+            return;
+        }
+
+        let dir =
+            std::env::temp_dir().join(format!("libcosmic-test-{}-golden", std::process::id()));
+        let golden = dir.join("window.png");
+        let actual = dir.join("window.actual.png");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut harness = harness();
+        let rendered = harness.render();
+
+        // golden images are not written unless asked to, so that a missing image fails
+        let missing = panic::catch_unwind(|| assert_golden(&rendered, &golden)).is_err();
+
+        rendered.save_png(&golden).unwrap();
+        let rendered_again = harness.render();
+        let same = panic::catch_unwind(|| assert_golden(&rendered_again, &golden)).is_ok();
+
+        let mut changed = rendered.clone();
+        changed.pixels[0] = changed.pixels[0].wrapping_add(128);
+        let differs = panic::catch_unwind(|| assert_golden(&changed, &golden)).is_err();
+        let written = Snapshot::load_png(&actual);
+
+        let _ = std::fs::remove_dir_all(dir);
+
+        assert!(missing);
+        assert!(same);
+        assert!(differs);
+        assert_eq!(written.unwrap(), changed);
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Shadows and outlines of elevated surfaces.
+
+pub use cosmic_theme::ElevationLevel;
+use iced_core::{renderer, BorderRadius, Color, Point, Rectangle, Size, Vector};
+
+/// Number of layers used to approximate the blur of a shadow.
+const BLUR_STEPS: u16 = 8;
+
+/// The appearance of an elevated surface.
+#[derive(Clone, Copy, Debug)]
+pub struct Appearance {
+    /// The color of the shadow.
+    pub shadow_color: Color,
+
+    /// The offset of the shadow from the surface.
+    pub shadow_offset: Vector,
+
+    /// The blur radius of the shadow.
+    pub blur_radius: f32,
+
+    /// The border radius of the surface.
+    pub border_radius: BorderRadius,
+
+    /// The width of the outline drawn in place of a shadow.
+    pub border_width: f32,
+
+    /// The color of the outline drawn in place of a shadow.
+    pub border_color: Color,
+}
+
+impl Default for Appearance {
+    fn default() -> Self {
+        Self {
+            shadow_color: Color::TRANSPARENT,
+            shadow_offset: Vector::default(),
+            blur_radius: 0.0,
+            border_radius: BorderRadius::from(0.0),
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+        }
+    }
+}
+
+/// A set of rules that dictate the [`Appearance`] of elevated surfaces.
+pub trait StyleSheet {
+    /// Produces the [`Appearance`] of a surface at the given elevation.
+    fn elevation(&self, level: ElevationLevel) -> Appearance;
+}
+
+/// Draws the shadow of a surface, which should be drawn before the surface itself.
+///
+/// The shadow is only drawn outside of `bounds`, so that it does not show through translucent
+/// surfaces.
+pub fn draw_shadow<Renderer: iced_core::Renderer>(
+    renderer: &mut Renderer,
+    bounds: Rectangle,
+    appearance: &Appearance,
+) {
+    if appearance.shadow_color.a <= 0.0 {
+        return;
+    }
+
+    let radii: [f32; 4] = appearance.border_radius.into();
+    let steps = if appearance.blur_radius > 0.0 {
+        BLUR_STEPS
+    } else {
+        1
+    };
+
+    // Overlapping translucent layers which grow outwards approximate a blurred shadow.
+    let color = Color {
+        a: appearance.shadow_color.a / f32::from(steps),
+        ..appearance.shadow_color
+    };
+
+    let shadow = Rectangle {
+        x: bounds.x + appearance.shadow_offset.x - appearance.blur_radius / 2.0,
+        y: bounds.y + appearance.shadow_offset.y - appearance.blur_radius / 2.0,
+        width: bounds.width + appearance.blur_radius,
+        height: bounds.height + appearance.blur_radius,
+    };
+
+    for clip in outside(shadow, bounds) {
+        renderer.with_layer(clip, |renderer| {
+            for step in 1..=steps {
+                let spread = appearance.blur_radius * f32::from(step) / f32::from(steps);
+
+                renderer.fill_quad(
+                    renderer::Quad {
+                        bounds: Rectangle {
+                            x: bounds.x + appearance.shadow_offset.x - spread / 2.0,
+                            y: bounds.y + appearance.shadow_offset.y - spread / 2.0,
+                            width: bounds.width + spread,
+                            height: bounds.height + spread,
+                        },
+                        border_radius: radii.map(|radius| radius + spread / 2.0).into(),
+                        border_width: 0.0,
+                        border_color: Color::TRANSPARENT,
+                    },
+                    color,
+                );
+            }
+        });
+    }
+}
+
+/// Splits the area of `outer` which is outside of `inner` into rectangles above, below, left
+/// and right of `inner`.
+fn outside(outer: Rectangle, inner: Rectangle) -> impl Iterator<Item = Rectangle> {
+    let left = outer.x.min(inner.x);
+    let right = (outer.x + outer.width).max(inner.x + inner.width);
+    let top = outer.y.min(inner.y);
+    let bottom = (outer.y + outer.height).max(inner.y + inner.height);
+    let inner_bottom = inner.y + inner.height;
+    let inner_right = inner.x + inner.width;
+
+    [
+        Rectangle::new(
+            Point::new(left, top),
+            Size::new(right - left, inner.y - top),
+        ),
+        Rectangle::new(
+            Point::new(left, inner_bottom),
+            Size::new(right - left, bottom - inner_bottom),
+        ),
+        Rectangle::new(
+            Point::new(left, inner.y),
+            Size::new(inner.x - left, inner.height),
+        ),
+        Rectangle::new(
+            Point::new(inner_right, inner.y),
+            Size::new(right - inner_right, inner.height),
+        ),
+    ]
+    .into_iter()
+    .filter(|clip| clip.width > 0.0 && clip.height > 0.0)
+}
+
+/// Draws the outline of a surface, which should be drawn after the surface itself.
+pub fn draw_outline<Renderer: iced_core::Renderer>(
+    renderer: &mut Renderer,
+    bounds: Rectangle,
+    appearance: &Appearance,
+) {
+    if appearance.border_width <= 0.0 {
+        return;
+    }
+
+    renderer.fill_quad(
+        renderer::Quad {
+            bounds,
+            border_radius: appearance.border_radius,
+            border_width: appearance.border_width,
+            border_color: appearance.border_color,
+        },
+        Color::TRANSPARENT,
+    );
+}
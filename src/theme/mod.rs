@@ -3,8 +3,11 @@
 
 //! Use COSMIC's themes and styles.
 
+pub mod elevation;
 pub mod expander;
+pub mod mode;
 mod segmented_button;
+mod transition;
 
 use std::cell::RefCell;
 use std::f32::consts::PI;
@@ -14,6 +17,7 @@ use std::rc::Rc;
 use std::sync::Arc;
 
 pub use self::segmented_button::SegmentedButton;
+pub use self::transition::Transition;
 
 use cosmic_config::config_subscription;
 use cosmic_config::CosmicConfigEntry;
@@ -21,6 +25,7 @@ use cosmic_theme::composite::over;
 use cosmic_theme::util::CssColor;
 use cosmic_theme::Component;
 use cosmic_theme::LayeredTheme;
+use cosmic_theme::ThemeBuilder;
 use iced_core::gradient::Linear;
 use iced_core::BorderRadius;
 use iced_core::Radians;
@@ -50,10 +55,11 @@ pub type CosmicComponent = cosmic_theme::Component<CosmicColor>;
 pub type CosmicTheme = cosmic_theme::Theme<CosmicColor>;
 
 lazy_static::lazy_static! {
-    pub static ref COSMIC_DARK: CosmicTheme = CosmicTheme::dark_default();
-    pub static ref COSMIC_HC_DARK: CosmicTheme = CosmicTheme::high_contrast_dark_default();
-    pub static ref COSMIC_LIGHT: CosmicTheme = CosmicTheme::light_default();
-    pub static ref COSMIC_HC_LIGHT: CosmicTheme = CosmicTheme::high_contrast_light_default();
+    // built from the palette config, so that vendor and user palettes apply to every theme type
+    pub static ref COSMIC_DARK: CosmicTheme = ThemeBuilder::active_dark().build();
+    pub static ref COSMIC_HC_DARK: CosmicTheme = ThemeBuilder::active_dark_high_contrast().build();
+    pub static ref COSMIC_LIGHT: CosmicTheme = ThemeBuilder::active_light().build();
+    pub static ref COSMIC_HC_LIGHT: CosmicTheme = ThemeBuilder::active_light_high_contrast().build();
     pub static ref TRANSPARENT_COMPONENT: Component<CosmicColor> = Component {
         base: CosmicColor::new(0.0, 0.0, 0.0, 0.0),
         hover: CosmicColor::new(0.0, 0.0, 0.0, 0.0),
@@ -74,6 +80,26 @@ thread_local! {
     pub(crate) static THEME: RefCell<Theme> = RefCell::new(Theme { theme_type: ThemeType::Dark, layer: cosmic_theme::Layer::Background });
 }
 
+/// Runs `f` with the default theme of the current thread temporarily replaced by `theme`.
+///
+/// Widgets read the default theme while they are constructed, so this is used by
+/// [`themed`](crate::widget::themed) to construct subtrees with a different theme.
+pub fn with_theme<R>(theme: &Theme, f: impl FnOnce() -> R) -> R {
+    /// Restores the previous theme, even if `f` panics.
+    struct Restore(Option<Theme>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            if let Some(theme) = self.0.take() {
+                THEME.with(|t| *t.borrow_mut() = theme);
+            }
+        }
+    }
+
+    let _restore = Restore(Some(THEME.with(|t| t.replace(theme.clone()))));
+    f()
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum ThemeType {
     #[default]
@@ -151,6 +177,15 @@ impl Theme {
         }
     }
 
+    /// preview how the theme looks with a color vision deficiency
+    #[must_use]
+    pub fn cvd_preview(&self, cvd: cosmic_theme::cvd::ColorVisionDeficiency) -> Self {
+        Self {
+            theme_type: ThemeType::Custom(Arc::new(cvd.simulate_theme(self.cosmic()))),
+            layer: self.layer,
+        }
+    }
+
     /// get current container
     /// can be used in a component that is intended to be a child of a `CosmicContainer`
     #[must_use]
@@ -194,10 +229,15 @@ impl application::StyleSheet for Theme {
         let cosmic = self.cosmic();
 
         match style {
-            Application::Default => application::Appearance {
-                background_color: cosmic.bg_color().into(),
-                text_color: cosmic.on_bg_color().into(),
-            },
+            Application::Default => {
+                let mut background = cosmic.bg_color();
+                background.alpha *= cosmic.window_opacity();
+
+                application::Appearance {
+                    background_color: background.into(),
+                    text_color: cosmic.on_bg_color().into(),
+                }
+            }
             Application::Custom(f) => f(self),
         }
     }
@@ -561,10 +601,12 @@ impl container::StyleSheet for Theme {
             Container::Custom(f) => f(self),
             Container::Background => {
                 let palette = self.cosmic();
+                let mut background = palette.background.base;
+                background.alpha *= palette.layer_opacity(cosmic_theme::Layer::Background);
 
                 container::Appearance {
                     text_color: Some(Color::from(palette.background.on)),
-                    background: Some(iced::Background::Color(palette.background.base.into())),
+                    background: Some(iced::Background::Color(background.into())),
                     border_radius: 2.0.into(),
                     border_width: 0.0,
                     border_color: Color::TRANSPARENT,
@@ -572,9 +614,11 @@ impl container::StyleSheet for Theme {
             }
             Container::HeaderBar => {
                 let palette = self.cosmic();
+                let opacity = palette.layer_opacity(cosmic_theme::Layer::Background);
                 let mut header_top = palette.background.base;
-                let header_bottom = palette.background.base;
-                header_top.alpha = 0.8;
+                let mut header_bottom = palette.background.base;
+                header_top.alpha = 0.8 * opacity;
+                header_bottom.alpha *= opacity;
 
                 container::Appearance {
                     text_color: Some(Color::from(palette.background.on)),
@@ -590,10 +634,12 @@ impl container::StyleSheet for Theme {
             }
             Container::Primary => {
                 let palette = self.cosmic();
+                let mut background = palette.primary.base;
+                background.alpha *= palette.layer_opacity(cosmic_theme::Layer::Primary);
 
                 container::Appearance {
                     text_color: Some(Color::from(palette.primary.on)),
-                    background: Some(iced::Background::Color(palette.primary.base.into())),
+                    background: Some(iced::Background::Color(background.into())),
                     border_radius: 2.0.into(),
                     border_width: 0.0,
                     border_color: Color::TRANSPARENT,
@@ -601,10 +647,12 @@ impl container::StyleSheet for Theme {
             }
             Container::Secondary => {
                 let palette = self.cosmic();
+                let mut background = palette.secondary.base;
+                background.alpha *= palette.layer_opacity(cosmic_theme::Layer::Secondary);
 
                 container::Appearance {
                     text_color: Some(Color::from(palette.secondary.on)),
-                    background: Some(iced::Background::Color(palette.secondary.base.into())),
+                    background: Some(iced::Background::Color(background.into())),
                     border_radius: 2.0.into(),
                     border_width: 0.0,
                     border_color: Color::TRANSPARENT,
@@ -647,6 +695,32 @@ impl container::StyleSheet for Theme {
     }
 }
 
+/*
+ * Elevation
+ */
+impl elevation::StyleSheet for Theme {
+    fn elevation(&self, level: elevation::ElevationLevel) -> elevation::Appearance {
+        let cosmic = self.cosmic();
+        let shadow = cosmic.elevation.shadow(level);
+
+        elevation::Appearance {
+            shadow_color: shadow.color.into(),
+            shadow_offset: iced_core::Vector::new(shadow.offset[0], shadow.offset[1]),
+            blur_radius: shadow.blur_radius,
+            border_radius: match level {
+                elevation::ElevationLevel::Flat | elevation::ElevationLevel::Raised => {
+                    cosmic.corner_radii.radius_s.into()
+                }
+                elevation::ElevationLevel::Overlay | elevation::ElevationLevel::Modal => {
+                    cosmic.corner_radii.radius_m.into()
+                }
+            },
+            border_width: shadow.border_width,
+            border_color: shadow.border.map_or(Color::TRANSPARENT, Color::from),
+        }
+    }
+}
+
 /*
  * Slider
  */
@@ -1230,12 +1304,14 @@ pub fn theme() -> Theme {
     ) else {
         return crate::theme::Theme::dark();
     };
-    let t = crate::cosmic_theme::Theme::get_entry(&helper).unwrap_or_else(|(errors, theme)| {
-        for err in errors {
-            tracing::error!("{:?}", err);
-        }
-        theme
-    });
+    let t = crate::cosmic_theme::Theme::<Srgba>::get_entry(&helper).unwrap_or_else(
+        |(errors, theme)| {
+            for err in errors {
+                tracing::error!("{:?}", err);
+            }
+            theme
+        },
+    );
     crate::theme::Theme::system(Arc::new(t))
 }
 
@@ -0,0 +1,95 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Switch between the light and dark themes, manually or on a schedule.
+
+use chrono::{Datelike, Local, Timelike};
+use cosmic_config::{config_subscription, CosmicConfigEntry};
+pub use cosmic_theme::{LocalTime, ThemeMode, ThemeSchedule};
+#[cfg(any(feature = "tokio", feature = "smol"))]
+use iced::futures::SinkExt;
+use iced_futures::Subscription;
+
+/// The current local time, for evaluating a [`ThemeSchedule`].
+#[must_use]
+#[allow(clippy::cast_possible_truncation)]
+pub fn local_time() -> LocalTime {
+    let now = Local::now();
+    LocalTime {
+        day_of_year: now.ordinal() as u16,
+        minute: (now.hour() * 60 + now.minute()) as u16,
+        utc_offset: (now.offset().local_minus_utc() / 60) as i16,
+    }
+}
+
+/// Get the configured [`ThemeMode`].
+#[must_use]
+pub fn mode() -> ThemeMode {
+    let Ok(config) = ThemeMode::config() else {
+        return ThemeMode::default();
+    };
+
+    ThemeMode::get_entry(&config).unwrap_or_else(|(errors, mode)| {
+        for err in errors {
+            tracing::error!("{:?}", err);
+        }
+        mode
+    })
+}
+
+/// Store a [`ThemeMode`] and activate the theme it selects.
+///
+/// # Errors
+///
+/// Returns an error if the mode or the theme could not be written.
+pub fn set_mode(mut mode: ThemeMode) -> Result<ThemeMode, cosmic_config::Error> {
+    mode.update(local_time());
+    mode.write_entry(&ThemeMode::config()?)?;
+    mode.apply()?;
+    Ok(mode)
+}
+
+/// Observe changes to the configured [`ThemeMode`].
+pub fn subscription(id: u64) -> Subscription<ThemeMode> {
+    config_subscription::<u64, ThemeMode>(id, cosmic_theme::MODE_ID.into(), ThemeMode::version())
+        .map(|(_, res)| {
+            res.unwrap_or_else(|(errors, mode)| {
+                for err in errors {
+                    tracing::error!("{:?}", err);
+                }
+                mode
+            })
+        })
+}
+
+/// Switches between the light and dark themes when the schedule of the
+/// [`ThemeMode`] says so, emitting the mode when started and then each minute.
+///
+/// This should only be run by one service in the session, such as the
+/// settings daemon, while applications observe the result with [`subscription`].
+#[cfg(any(feature = "tokio", feature = "smol"))]
+pub fn scheduler() -> Subscription<ThemeMode> {
+    // the schedule is evaluated once at startup instead of waiting for the first minute
+    let startup = iced::subscription::channel(
+        std::any::TypeId::of::<ThemeSchedule>(),
+        1,
+        |mut output| async move {
+            let _res = output.send(()).await;
+            iced::futures::future::pending().await
+        },
+    );
+
+    Subscription::batch([
+        startup,
+        iced::time::every(std::time::Duration::from_secs(60)).map(|_| ()),
+    ])
+    .map(|()| {
+        let mut mode = mode();
+        if mode.update(local_time()) {
+            if let Err(err) = set_mode(mode.clone()) {
+                tracing::error!("failed to switch theme mode: {:?}", err);
+            }
+        }
+        mode
+    })
+}
@@ -0,0 +1,62 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Cross-fades between themes.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::CosmicTheme;
+
+/// An in-progress cross-fade from one theme to another.
+#[derive(Debug, Clone)]
+pub struct Transition {
+    from: Arc<CosmicTheme>,
+    to: Arc<CosmicTheme>,
+    start: Instant,
+    duration: Duration,
+}
+
+impl Transition {
+    /// Starts a cross-fade between two themes.
+    #[must_use]
+    pub fn new(from: Arc<CosmicTheme>, to: Arc<CosmicTheme>, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            start: Instant::now(),
+            duration,
+        }
+    }
+
+    /// Eased progress of the cross-fade, from `0.0` to `1.0`.
+    #[must_use]
+    pub fn progress(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+
+        let t = (now.saturating_duration_since(self.start).as_secs_f32()
+            / self.duration.as_secs_f32())
+        .clamp(0.0, 1.0);
+
+        // Smoothstep easing
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    /// Whether the cross-fade has completed.
+    #[must_use]
+    pub fn is_finished(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.start) >= self.duration
+    }
+
+    /// The interpolated theme at the given instant.
+    #[must_use]
+    pub fn theme(&self, now: Instant) -> Arc<CosmicTheme> {
+        if self.is_finished(now) {
+            return self.to.clone();
+        }
+
+        Arc::new(self.from.mix(&self.to, self.progress(now)))
+    }
+}
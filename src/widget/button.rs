@@ -6,6 +6,7 @@ use crate::{
     Element, Renderer,
 };
 use iced::widget;
+use iced_core::{Alignment, Length};
 
 /// A button widget with COSMIC styling
 #[must_use]
@@ -49,7 +50,21 @@ impl<Message: 'static> Button<Message> {
     pub fn custom(self, children: Vec<Element<Message>>) -> widget::Button<Message, Renderer> {
         let theme = THEME.with(|t| t.borrow().clone());
         let theme = theme.cosmic();
-        let button = widget::button(widget::row(children).spacing(8))
+        let content = widget::row(children)
+            .spacing(theme.space_xxs())
+            .align_items(Alignment::Center);
+        // an empty space with the height of the button content keeps the button from shrinking
+        // below the minimum height of the layout, while taller content may still grow it
+        let min_height = theme
+            .layout
+            .button_height
+            .saturating_sub(2 * theme.space_xxs());
+        let content = widget::row(vec![
+            widget::Space::new(Length::Fixed(0.0), Length::Fixed(f32::from(min_height))).into(),
+            content.into(),
+        ])
+        .align_items(Alignment::Center);
+        let button = widget::button(content)
             .style(self.style)
             .padding([theme.space_xxs(), theme.space_s()]);
 
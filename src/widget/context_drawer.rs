@@ -0,0 +1,104 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! A panel on the right side of the window, which shows details of the selected content.
+//!
+//! Applications show a context drawer by returning it from
+//! [`Application::context_drawer`](crate::Application::context_drawer) while it is opened with
+//! [`Core::set_context_drawer_active`](crate::app::Core::set_context_drawer_active).
+
+use crate::theme::{self, THEME};
+use crate::Element;
+use iced::widget::{column, horizontal_space, row};
+use iced_core::{Alignment, Color, Length};
+use std::borrow::Cow;
+
+/// Width of the context drawer, when the window is wide enough for it.
+pub const WIDTH: f32 = 360.0;
+
+/// A context drawer with a title, which shows `content`.
+pub fn context_drawer<'a, Message>(
+    title: impl Into<Cow<'a, str>>,
+    content: impl Into<Element<'a, Message>>,
+) -> ContextDrawer<'a, Message> {
+    ContextDrawer {
+        title: title.into(),
+        content: content.into(),
+        on_close: None,
+    }
+}
+
+/// A panel with a header, which shows details of the selected content.
+pub struct ContextDrawer<'a, Message> {
+    title: Cow<'a, str>,
+    content: Element<'a, Message>,
+    on_close: Option<Message>,
+}
+
+impl<'a, Message: 'static> ContextDrawer<'a, Message> {
+    /// Emits a message when the close button of the header is pressed.
+    #[must_use]
+    pub fn on_close(mut self, message: Message) -> Self {
+        self.on_close = Some(message);
+        self
+    }
+
+    /// Applies a transformation to the messages of the drawer.
+    pub fn map<B: 'static>(
+        self,
+        f: impl Fn(Message) -> B + Clone + 'static,
+    ) -> ContextDrawer<'a, B> {
+        ContextDrawer {
+            title: self.title,
+            on_close: self.on_close.map(f.clone()),
+            content: self.content.map(f),
+        }
+    }
+}
+
+impl<'a, Message: 'static> From<ContextDrawer<'a, Message>> for Element<'a, Message> {
+    fn from(drawer: ContextDrawer<'a, Message>) -> Self {
+        let (space_xxs, space_s, space_m, radius_s) = THEME.with(|theme| {
+            let theme = theme.borrow();
+            let cosmic = theme.cosmic();
+            (
+                cosmic.space_xxs(),
+                cosmic.space_s(),
+                cosmic.space_m(),
+                cosmic.radius_s(),
+            )
+        });
+
+        let mut header = row![
+            crate::widget::text::heading(drawer.title),
+            horizontal_space(Length::Fill),
+        ]
+        .align_items(Alignment::Center);
+
+        if let Some(message) = drawer.on_close {
+            header = header.push(
+                crate::widget::button(theme::Button::Text)
+                    .on_press(message)
+                    .icon(theme::Svg::Symbolic, "window-close-symbolic", 16),
+            );
+        }
+
+        let content = crate::widget::scrollable(drawer.content).height(Length::Fill);
+
+        crate::widget::container(column![header, content].spacing(space_s))
+            .padding([space_xxs, space_m, space_m, space_m])
+            .width(Length::Fixed(WIDTH))
+            .height(Length::Fill)
+            .style(theme::Container::custom(move |theme| {
+                let cosmic = theme.cosmic();
+                iced_style::container::Appearance {
+                    text_color: Some(cosmic.primary.on.into()),
+                    background: Some(Color::from(cosmic.primary.base).into()),
+                    border_radius: radius_s.into(),
+                    border_width: 0.0,
+                    border_color: Color::TRANSPARENT,
+                }
+            }))
+            .into()
+    }
+}
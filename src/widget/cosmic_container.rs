@@ -1,3 +1,4 @@
+use crate::theme::elevation::{self, ElevationLevel};
 use cosmic_theme::LayeredTheme;
 use iced::widget::Container;
 use iced_core::alignment;
@@ -29,6 +30,7 @@ where
     Renderer::Theme: StyleSheet + Clone + cosmic_theme::LayeredTheme,
 {
     layer: Option<cosmic_theme::Layer>,
+    elevation: Option<ElevationLevel>,
     container: Container<'a, Message, Renderer>,
 }
 
@@ -45,6 +47,7 @@ where
     {
         LayerContainer {
             layer: None,
+            elevation: None,
             container: Container::new(content),
         }
     }
@@ -60,6 +63,13 @@ where
         })
     }
 
+    /// Sets the [`ElevationLevel`] of the [`LayerContainer`], drawing its shadow.
+    #[must_use]
+    pub fn elevation(mut self, elevation: ElevationLevel) -> Self {
+        self.elevation = Some(elevation);
+        self
+    }
+
     /// Sets the [`Padding`] of the [`LayerContainer`].
     #[must_use]
     pub fn padding<P: Into<Padding>>(mut self, padding: P) -> Self {
@@ -134,7 +144,7 @@ where
 impl<'a, Message, Renderer> Widget<Message, Renderer> for LayerContainer<'a, Message, Renderer>
 where
     Renderer: iced_core::Renderer,
-    Renderer::Theme: StyleSheet + Clone + cosmic_theme::LayeredTheme,
+    Renderer::Theme: StyleSheet + elevation::StyleSheet + Clone + cosmic_theme::LayeredTheme,
 {
     fn children(&self) -> Vec<Tree> {
         self.container.children()
@@ -213,6 +223,13 @@ where
         cursor_position: mouse::Cursor,
         viewport: &Rectangle,
     ) {
+        let elevation = self
+            .elevation
+            .map(|level| elevation::StyleSheet::elevation(theme, level));
+        if let Some(appearance) = elevation.as_ref() {
+            elevation::draw_shadow(renderer, layout.bounds(), appearance);
+        }
+
         let theme = if let Some(layer) = self.layer {
             let mut theme = theme.clone();
             theme.set_layer(layer);
@@ -229,6 +246,10 @@ where
             cursor_position,
             viewport,
         );
+
+        if let Some(appearance) = elevation.as_ref() {
+            elevation::draw_outline(renderer, layout.bounds(), appearance);
+        }
     }
 
     fn overlay<'b>(
@@ -246,7 +267,7 @@ impl<'a, Message, Renderer> From<LayerContainer<'a, Message, Renderer>>
 where
     Message: 'a,
     Renderer: 'a + iced_core::Renderer,
-    Renderer::Theme: StyleSheet + Clone + cosmic_theme::LayeredTheme,
+    Renderer::Theme: StyleSheet + elevation::StyleSheet + Clone + cosmic_theme::LayeredTheme,
 {
     fn from(column: LayerContainer<'a, Message, Renderer>) -> Element<'a, Message, Renderer> {
         Element::new(column)
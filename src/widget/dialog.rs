@@ -0,0 +1,556 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Dialogs which block the rest of the window until the user responds to them.
+//!
+//! Applications show a [`Dialog`] by returning it from
+//! [`Application::dialog`](crate::Application::dialog), which is drawn above a scrim over the
+//! window by [`modal`].
+
+use crate::theme::elevation::ElevationLevel;
+use crate::theme::{self, THEME};
+use crate::widget::icon::{self, IconSource};
+use crate::{Element, Renderer, Theme};
+use iced::widget::{checkbox, column, horizontal_space, row};
+use iced_core::event::{self, Event};
+use iced_core::keyboard::{self, KeyCode};
+use iced_core::widget::{Operation, OperationOutputWrapper, Tree};
+use iced_core::{alignment, layout, mouse, overlay, renderer, touch};
+use iced_core::{Alignment, Background, Clipboard, Color, Layout, Length, Point, Rectangle};
+use iced_core::{Shell, Size, Widget};
+use std::borrow::Cow;
+
+/// A dialog with a title and a primary action.
+pub fn dialog<'a, Message>(title: impl Into<Cow<'a, str>>) -> Dialog<'a, Message> {
+    Dialog::new(title)
+}
+
+/// A dialog with a title, body, icon, actions, and an optional text entry or checkbox.
+pub struct Dialog<'a, Message> {
+    title: Cow<'a, str>,
+    body: Option<Cow<'a, str>>,
+    icon: Option<IconSource<'a>>,
+    primary_action: Option<(Cow<'a, str>, Message, theme::Button)>,
+    secondary_action: Option<(Cow<'a, str>, Message)>,
+    control: Option<Control<'a, Message>>,
+}
+
+/// The control below the body of a dialog.
+enum Control<'a, Message> {
+    /// A text entry, which is created with the dialog so that it submits the primary action
+    /// whichever order they were added in.
+    TextInput {
+        placeholder: String,
+        value: String,
+        on_input: Box<dyn Fn(String) -> Message + 'a>,
+    },
+    Element(Element<'a, Message>),
+}
+
+impl<'a, Message: 'static> Dialog<'a, Message> {
+    /// A dialog with a title.
+    pub fn new(title: impl Into<Cow<'a, str>>) -> Self {
+        Self {
+            title: title.into(),
+            body: None,
+            icon: None,
+            primary_action: None,
+            secondary_action: None,
+            control: None,
+        }
+    }
+
+    /// Text explaining what the dialog is asking of the user.
+    #[must_use]
+    pub fn body(mut self, body: impl Into<Cow<'a, str>>) -> Self {
+        self.body = Some(body.into());
+        self
+    }
+
+    /// An icon shown beside the title and body.
+    #[must_use]
+    pub fn icon(mut self, icon: impl Into<IconSource<'a>>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// The suggested action, which emits `message` when pressed.
+    #[must_use]
+    pub fn primary_action(mut self, label: impl Into<Cow<'a, str>>, message: Message) -> Self {
+        self.primary_action = Some((label.into(), message, theme::Button::Primary));
+        self
+    }
+
+    /// A primary action which destroys data, such as deleting a file.
+    #[must_use]
+    pub fn destructive_action(mut self, label: impl Into<Cow<'a, str>>, message: Message) -> Self {
+        self.primary_action = Some((label.into(), message, theme::Button::Destructive));
+        self
+    }
+
+    /// An alternative to the primary action, such as cancelling the dialog.
+    #[must_use]
+    pub fn secondary_action(mut self, label: impl Into<Cow<'a, str>>, message: Message) -> Self {
+        self.secondary_action = Some((label.into(), message));
+        self
+    }
+
+    /// A text entry below the body, which submits the primary action when Enter is pressed.
+    #[must_use]
+    pub fn text_input(
+        mut self,
+        placeholder: &str,
+        value: &str,
+        on_input: impl Fn(String) -> Message + 'a,
+    ) -> Self {
+        self.control = Some(Control::TextInput {
+            placeholder: placeholder.to_owned(),
+            value: value.to_owned(),
+            on_input: Box::new(on_input),
+        });
+        self
+    }
+
+    /// A checkbox below the body, such as "Do not ask again".
+    #[must_use]
+    pub fn checkbox(
+        mut self,
+        label: impl Into<String>,
+        checked: bool,
+        on_toggle: impl Fn(bool) -> Message + 'a,
+    ) -> Self {
+        self.control = Some(Control::Element(checkbox(label, checked, on_toggle).into()));
+        self
+    }
+}
+
+impl<'a, Message: Clone + 'static> From<Dialog<'a, Message>> for Element<'a, Message> {
+    fn from(dialog: Dialog<'a, Message>) -> Self {
+        let (space_xxs, space_s, space_m, radius_m) = THEME.with(|theme| {
+            let theme = theme.borrow();
+            let cosmic = theme.cosmic();
+            (
+                cosmic.space_xxs(),
+                cosmic.space_s(),
+                cosmic.space_m(),
+                cosmic.radius_m(),
+            )
+        });
+
+        let mut text = column![crate::widget::text::heading(dialog.title)].spacing(space_xxs);
+        if let Some(body) = dialog.body {
+            text = text.push(crate::widget::text::body(body));
+        }
+
+        let mut content = row![].spacing(space_m);
+        if let Some(icon) = dialog.icon {
+            content = content.push(icon::icon(icon, 64));
+        }
+        content = content.push(text.width(Length::Fill));
+
+        let control: Option<Element<'a, Message>> = match dialog.control {
+            Some(Control::TextInput {
+                placeholder,
+                value,
+                on_input,
+            }) => {
+                let mut input = crate::widget::text_input(&placeholder, &value).on_input(on_input);
+                if let Some((_, message, _)) = dialog.primary_action.as_ref() {
+                    input = input.on_submit(message.clone());
+                }
+                Some(input.into())
+            }
+            Some(Control::Element(control)) => Some(control),
+            None => None,
+        };
+
+        let mut actions = row![horizontal_space(Length::Fill)]
+            .spacing(space_xxs)
+            .align_items(Alignment::Center);
+        if let Some((label, message)) = dialog.secondary_action {
+            actions = actions.push(
+                crate::widget::button(theme::Button::Secondary)
+                    .on_press(message)
+                    .text(&label),
+            );
+        }
+        if let Some((label, message, style)) = dialog.primary_action {
+            actions = actions.push(crate::widget::button(style).on_press(message).text(&label));
+        }
+
+        let mut column = column![content].spacing(space_s);
+        if let Some(control) = control {
+            column = column.push(control);
+        }
+        column = column.push(actions);
+
+        crate::widget::container(column)
+            .padding(space_m)
+            .max_width(570.0)
+            .style(theme::Container::custom(move |theme| {
+                let cosmic = theme.cosmic();
+                iced_style::container::Appearance {
+                    text_color: Some(cosmic.background.on.into()),
+                    background: Some(Color::from(cosmic.background.base).into()),
+                    border_radius: radius_m.into(),
+                    border_width: 0.0,
+                    border_color: Color::TRANSPARENT,
+                }
+            }))
+            .elevation(ElevationLevel::Modal)
+            .into()
+    }
+}
+
+/// Shows `dialog` centered above a scrim over `base`, which is blocked while it is shown.
+///
+/// Focus is kept within the dialog. Keys which it ignores are captured so that they do not trigger
+/// the shortcuts of the application behind it, except for Escape and Tab, which are left to the
+/// keyboard shortcuts that dismiss the dialog and move focus within it.
+pub fn modal<'a, Message>(
+    base: impl Into<Element<'a, Message>>,
+    dialog: Option<Element<'a, Message>>,
+) -> Modal<'a, Message> {
+    Modal {
+        base: base.into(),
+        dialog,
+        align_x: alignment::Horizontal::Center,
+        on_scrim_press: None,
+    }
+}
+
+/// Shows a dialog above its base content, which is blocked while the dialog is shown.
+pub struct Modal<'a, Message> {
+    base: Element<'a, Message>,
+    dialog: Option<Element<'a, Message>>,
+    align_x: alignment::Horizontal,
+    on_scrim_press: Option<Message>,
+}
+
+impl<'a, Message> Modal<'a, Message> {
+    /// Aligns the dialog to a side of the base, rather than centering it.
+    #[must_use]
+    pub fn align_x(mut self, align_x: alignment::Horizontal) -> Self {
+        self.align_x = align_x;
+        self
+    }
+
+    /// Emits a message when the scrim around the dialog is pressed, such as to dismiss it.
+    #[must_use]
+    pub fn on_scrim_press(mut self, message: Message) -> Self {
+        self.on_scrim_press = Some(message);
+        self
+    }
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for Modal<'a, Message> {
+    fn children(&self) -> Vec<Tree> {
+        match self.dialog.as_ref() {
+            Some(dialog) => vec![Tree::new(&self.base), Tree::new(dialog)],
+            None => vec![Tree::new(&self.base)],
+        }
+    }
+
+    fn diff(&mut self, tree: &mut Tree) {
+        match self.dialog.as_mut() {
+            Some(dialog) => tree.diff_children(&mut [&mut self.base, dialog]),
+            None => tree.diff_children(&mut [&mut self.base]),
+        }
+    }
+
+    fn width(&self) -> Length {
+        self.base.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.base.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.base.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<OperationOutputWrapper<Message>>,
+    ) {
+        // the base is skipped while the dialog is shown, so that focus can not leave the dialog
+        if self.dialog.is_none() {
+            self.base
+                .as_widget()
+                .operate(&mut tree.children[0], layout, renderer, operation);
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        if self.dialog.is_some() {
+            return event::Status::Ignored;
+        }
+
+        self.base.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if self.dialog.is_some() {
+            return mouse::Interaction::Idle;
+        }
+
+        self.base.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let cursor_position = if self.dialog.is_some() {
+            mouse::Cursor::Unavailable
+        } else {
+            cursor_position
+        };
+
+        self.base.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        let Some(dialog) = self.dialog.as_mut() else {
+            return self
+                .base
+                .as_widget_mut()
+                .overlay(&mut tree.children[0], layout, renderer);
+        };
+
+        Some(overlay::Element::new(
+            layout.position(),
+            Box::new(ModalOverlay {
+                tree: &mut tree.children[1],
+                content: dialog,
+                size: layout.bounds().size(),
+                align_x: self.align_x,
+                on_scrim_press: self.on_scrim_press.as_ref(),
+            }),
+        ))
+    }
+}
+
+impl<'a, Message: Clone + 'a> From<Modal<'a, Message>> for Element<'a, Message> {
+    fn from(modal: Modal<'a, Message>) -> Self {
+        Self::new(modal)
+    }
+}
+
+/// Draws the scrim, and the dialog above it.
+struct ModalOverlay<'a, 'b, Message> {
+    tree: &'a mut Tree,
+    content: &'a mut Element<'b, Message>,
+    /// Size of the base which the scrim covers.
+    size: Size,
+    align_x: alignment::Horizontal,
+    on_scrim_press: Option<&'a Message>,
+}
+
+impl<'a, 'b, Message: Clone> overlay::Overlay<Message, Renderer> for ModalOverlay<'a, 'b, Message> {
+    fn layout(&self, renderer: &Renderer, _bounds: Size, position: Point) -> layout::Node {
+        let limits = layout::Limits::new(Size::ZERO, self.size);
+        let mut dialog = self.content.as_widget().layout(renderer, &limits);
+
+        let free = Size::new(
+            (self.size.width - dialog.size().width).max(0.0),
+            (self.size.height - dialog.size().height).max(0.0),
+        );
+        let x = match self.align_x {
+            alignment::Horizontal::Left => 0.0,
+            alignment::Horizontal::Center => free.width / 2.0,
+            alignment::Horizontal::Right => free.width,
+        };
+        dialog.move_to(Point::new(x, free.height / 2.0));
+
+        let mut node = layout::Node::with_children(self.size, vec![dialog]);
+        node.move_to(position);
+        node
+    }
+
+    fn operate(
+        &mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<OperationOutputWrapper<Message>>,
+    ) {
+        if let Some(dialog) = layout.children().next() {
+            self.content
+                .as_widget()
+                .operate(self.tree, dialog, renderer, operation);
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        let Some(dialog) = layout.children().next() else {
+            return event::Status::Ignored;
+        };
+
+        let is_press = matches!(
+            event,
+            Event::Mouse(mouse::Event::ButtonPressed(_))
+                | Event::Touch(touch::Event::FingerPressed { .. })
+        );
+        let is_shortcut = matches!(
+            event,
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. })
+                if !matches!(key_code, KeyCode::Escape | KeyCode::Tab)
+        );
+
+        let status = self.content.as_widget_mut().on_event(
+            self.tree,
+            event,
+            dialog,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+            &layout.bounds(),
+        );
+
+        if let (event::Status::Ignored, true, Some(message)) =
+            (status, is_press, self.on_scrim_press)
+        {
+            if !cursor_position.is_over(dialog.bounds()) {
+                shell.publish(message.clone());
+                return event::Status::Captured;
+            }
+        }
+
+        if let (event::Status::Ignored, true) = (status, is_shortcut) {
+            return event::Status::Captured;
+        }
+
+        status
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        layout
+            .children()
+            .next()
+            .map_or(mouse::Interaction::Idle, |dialog| {
+                self.content.as_widget().mouse_interaction(
+                    self.tree,
+                    dialog,
+                    cursor_position,
+                    viewport,
+                    renderer,
+                )
+            })
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+    ) {
+        let bounds = layout.bounds();
+        let cosmic = theme.cosmic();
+        let scrim = Color {
+            a: if cosmic.is_dark { 0.5 } else { 0.3 },
+            ..Color::BLACK
+        };
+
+        renderer::Renderer::fill_quad(
+            renderer,
+            renderer::Quad {
+                bounds,
+                border_radius: 0.0.into(),
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+            },
+            Background::Color(scrim),
+        );
+
+        let Some(dialog) = layout.children().next() else {
+            return;
+        };
+
+        self.content.as_widget().draw(
+            self.tree,
+            renderer,
+            theme,
+            style,
+            dialog,
+            cursor_position,
+            &bounds,
+        );
+    }
+
+    fn is_over(&self, _layout: Layout<'_>, _renderer: &Renderer, _cursor_position: Point) -> bool {
+        // the scrim blocks the pointer from reaching the base
+        true
+    }
+}
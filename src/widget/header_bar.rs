@@ -1,7 +1,10 @@
 // Copyright 2022 System76 <info@system76.com>
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::{theme, Element};
+use crate::{
+    theme::{self, THEME},
+    Element,
+};
 use apply::Apply;
 use derive_setters::Setters;
 use iced::{self, widget, Length};
@@ -132,11 +135,17 @@ impl<'a, Message: Clone + 'static> HeaderBar<'a, Message> {
                 .into(),
         );
 
+        let (space_xxs, header_bar_height) = THEME.with(|t| {
+            let theme = t.borrow();
+            let cosmic = theme.cosmic();
+            (cosmic.space_xxs(), cosmic.layout.header_bar_height)
+        });
+
         // Creates the headerbar widget.
         let mut widget = widget::row(packed)
-            .height(Length::Fixed(50.0))
-            .padding(8)
-            .spacing(8)
+            .height(Length::Fixed(f32::from(header_bar_height)))
+            .padding(space_xxs)
+            .spacing(space_xxs)
             .apply(widget::container)
             .style(crate::theme::Container::HeaderBar)
             .center_y()
@@ -159,9 +168,7 @@ impl<'a, Message: Clone + 'static> HeaderBar<'a, Message> {
         let mut title = Cow::default();
         std::mem::swap(&mut title, &mut self.title);
 
-        super::text(title)
-            .size(16)
-            .font(crate::font::FONT_SEMIBOLD)
+        super::text::heading(title)
             .apply(widget::container)
             .center_x()
             .center_y()
@@ -1,7 +1,11 @@
 // Copyright 2022 System76 <info@system76.com>
 // SPDX-License-Identifier: MPL-2.0
 
-use crate::{theme, widget::divider, Element};
+use crate::{
+    theme::{self, THEME},
+    widget::divider,
+    Element,
+};
 use apply::Apply;
 use iced::{Background, Color};
 
@@ -41,10 +45,19 @@ impl<'a, Message: 'static> ListColumn<'a, Message> {
 
     #[must_use]
     pub fn into_element(self) -> Element<'a, Message> {
+        let (spacing, padding) = THEME.with(|t| {
+            let theme = t.borrow();
+            let cosmic = theme.cosmic();
+            (
+                cosmic.space_xs(),
+                [cosmic.space_s(), cosmic.layout.list_padding],
+            )
+        });
+
         iced::widget::column(self.children)
-            .spacing(12)
+            .spacing(spacing)
             .apply(iced::widget::container)
-            .padding([16, 6])
+            .padding(padding)
             .style(theme::Container::custom(style))
             .into()
     }
@@ -63,7 +76,7 @@ pub fn style(theme: &crate::Theme) -> iced::widget::container::Appearance {
     iced::widget::container::Appearance {
         text_color: Some(container.on.into()),
         background: Some(Background::Color(container.base.into())),
-        border_radius: 8.0.into(),
+        border_radius: theme.cosmic().corner_radii.radius_s.into(),
         border_width: 0.0,
         border_color: Color::TRANSPARENT,
     }
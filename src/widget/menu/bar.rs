@@ -0,0 +1,238 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! A row of menus, which is typically placed in a header bar.
+
+use super::overlay::{self as menu_overlay, MenuOverlay, MenuState, Metrics};
+use super::Menu;
+use crate::{Element, Renderer, Theme};
+use iced_core::event::{self, Event};
+use iced_core::keyboard::{self, KeyCode};
+use iced_core::widget::{tree, Tree};
+use iced_core::{layout, mouse, overlay, renderer, touch};
+use iced_core::{Clipboard, Layout, Length, Point, Rectangle, Shell, Size, Widget};
+
+/// A row of menus, which open below their labels.
+///
+/// F10 opens the first menu, and Alt with the mnemonic of a menu opens that menu.
+pub fn menu_bar<'a, Message>(menus: Vec<Menu<'a, Message>>) -> MenuBar<'a, Message> {
+    MenuBar::new(menus)
+}
+
+/// A row of menus, which open below their labels.
+pub struct MenuBar<'a, Message> {
+    menus: Vec<Menu<'a, Message>>,
+    metrics: Metrics,
+}
+
+impl<'a, Message> MenuBar<'a, Message> {
+    /// A menu bar showing `menus`.
+    pub fn new(menus: Vec<Menu<'a, Message>>) -> Self {
+        Self {
+            menus,
+            metrics: Metrics::from_theme(),
+        }
+    }
+
+    /// The index of the menu whose label is under the cursor.
+    fn menu_at(layout: Layout<'_>, cursor_position: mouse::Cursor) -> Option<usize> {
+        layout
+            .children()
+            .position(|label| cursor_position.is_over(label.bounds()))
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    menu: MenuState,
+    /// Whether Alt is held, which shows the mnemonics of the menus.
+    alt: bool,
+    /// The menu whose label is under the cursor.
+    hovered: Option<usize>,
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for MenuBar<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn width(&self) -> Length {
+        Length::Shrink
+    }
+
+    fn height(&self) -> Length {
+        Length::Shrink
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let mut x = 0.0;
+
+        let children = self
+            .menus
+            .iter()
+            .map(|menu| {
+                let width = menu_overlay::measure(renderer, &menu.label.text)
+                    + self.metrics.item_padding * 2.0;
+                let mut node = layout::Node::new(Size::new(width, self.metrics.item_height));
+                node.move_to(Point::new(x, 0.0));
+                x += width;
+                node
+            })
+            .collect();
+
+        let size = limits.resolve(Size::new(x, self.metrics.item_height));
+        layout::Node::with_children(size, children)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        _shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+
+        match event {
+            Event::Keyboard(keyboard::Event::ModifiersChanged(modifiers)) => {
+                state.alt = modifiers.alt();
+            }
+
+            Event::Mouse(mouse::Event::CursorMoved { .. })
+            | Event::Touch(touch::Event::FingerMoved { .. }) => {
+                state.hovered = Self::menu_at(layout, cursor_position);
+
+                // moving across the bar switches between menus while one is open
+                if let (Some(open), Some(hovered)) = (state.menu.open, state.hovered) {
+                    if open != hovered {
+                        state.menu.open(hovered, &self.menus[hovered].items, false);
+                    }
+                }
+            }
+
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => {
+                if let Some(index) = Self::menu_at(layout, cursor_position) {
+                    if state.menu.open == Some(index) {
+                        state.menu.close();
+                    } else {
+                        state.menu.open(index, &self.menus[index].items, false);
+                    }
+
+                    return event::Status::Captured;
+                }
+            }
+
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                modifiers,
+            }) if !state.menu.is_open() => {
+                let index = if key_code == KeyCode::F10 && modifiers.is_empty() {
+                    (!self.menus.is_empty()).then_some(0)
+                } else if modifiers.alt() {
+                    menu_overlay::mnemonic_char(key_code).and_then(|mnemonic| {
+                        self.menus
+                            .iter()
+                            .position(|menu| menu.label.mnemonic.map(|(_, c)| c) == Some(mnemonic))
+                    })
+                } else {
+                    None
+                };
+
+                if let Some(index) = index {
+                    state.menu.open(index, &self.menus[index].items, true);
+                    return event::Status::Captured;
+                }
+            }
+
+            _ => (),
+        }
+
+        event::Status::Ignored
+    }
+
+    fn mouse_interaction(
+        &self,
+        _tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        if Self::menu_at(layout, cursor_position).is_some() {
+            mouse::Interaction::Pointer
+        } else {
+            mouse::Interaction::Idle
+        }
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        let state = tree.state.downcast_ref::<State>();
+        let cosmic = theme.cosmic();
+
+        for (index, (menu, label)) in self.menus.iter().zip(layout.children()).enumerate() {
+            let bounds = label.bounds();
+
+            if state.menu.open == Some(index) {
+                let pressed = cosmic.background.component.pressed.into();
+                menu_overlay::fill(renderer, bounds, self.metrics.radius, pressed);
+            } else if state.hovered == Some(index) {
+                let hover = cosmic.background.component.hover.into();
+                menu_overlay::fill(renderer, bounds, self.metrics.radius, hover);
+            }
+
+            menu_overlay::draw_label(
+                renderer,
+                &menu.label,
+                Point::new(bounds.x + self.metrics.item_padding, bounds.center_y()),
+                cosmic.background.on.into(),
+                state.alt,
+            );
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        _renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        let state = tree.state.downcast_mut::<State>();
+        state.menu.clamp(&self.menus);
+        let anchor = layout.children().nth(state.menu.open?)?.bounds();
+
+        let overlay = MenuOverlay {
+            state: &mut state.menu,
+            menus: &self.menus,
+            anchor: anchor.size(),
+            passthrough: layout.bounds(),
+            switch_menus: true,
+            metrics: self.metrics,
+        };
+
+        Some(overlay.element(anchor.position()))
+    }
+}
+
+impl<'a, Message: Clone + 'a> From<MenuBar<'a, Message>> for Element<'a, Message> {
+    fn from(menu_bar: MenuBar<'a, Message>) -> Self {
+        Self::new(menu_bar)
+    }
+}
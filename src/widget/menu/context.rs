@@ -0,0 +1,299 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! A menu which opens at the pointer when an element is right-clicked.
+
+use super::overlay::{MenuOverlay, MenuState, Metrics};
+use super::{Item, Menu};
+use crate::{Element, Renderer, Theme};
+use iced_core::event::{self, Event};
+use iced_core::keyboard::{self, KeyCode};
+use iced_core::widget::{operation, tree, Id, Operation, OperationOutputWrapper, Tree};
+use iced_core::{layout, mouse, overlay, renderer};
+use iced_core::{Clipboard, Layout, Length, Point, Rectangle, Shell, Size, Widget};
+
+/// Opens a menu of `items` at the pointer when `content` is right-clicked.
+///
+/// The menu is also opened by the Menu key and Shift+F10 while `content` or one of its widgets
+/// has focus, or while the pointer is over `content`.
+pub fn context_menu<'a, Message>(
+    content: impl Into<Element<'a, Message>>,
+    items: Vec<Item<'a, Message>>,
+) -> ContextMenu<'a, Message> {
+    ContextMenu::new(content, items)
+}
+
+/// Where a context menu which is shown in its own popup surface should be opened.
+#[cfg(feature = "wayland")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ContextMenuPopup {
+    /// Position in the window which the menu was opened from, at the pointer or, when opened
+    /// with the keyboard, at the content.
+    pub position: Point,
+    /// Size of the menu, with room for its submenus to open beside it.
+    pub size: Size,
+}
+
+/// Opens a menu at the pointer when its content is right-clicked.
+pub struct ContextMenu<'a, Message> {
+    content: Element<'a, Message>,
+    menu: Menu<'a, Message>,
+    metrics: Metrics,
+    #[cfg(feature = "wayland")]
+    on_popup: Option<Box<dyn Fn(ContextMenuPopup) -> Message + 'a>>,
+}
+
+impl<'a, Message> ContextMenu<'a, Message> {
+    /// Opens a menu of `items` when `content` is right-clicked.
+    pub fn new(content: impl Into<Element<'a, Message>>, items: Vec<Item<'a, Message>>) -> Self {
+        Self {
+            content: content.into(),
+            menu: Menu::new("", items),
+            metrics: Metrics::from_theme(),
+            #[cfg(feature = "wayland")]
+            on_popup: None,
+        }
+    }
+
+    /// Shows the menu in a popup surface which may extend beyond the window, instead of an
+    /// overlay.
+    ///
+    /// Rather than opening the menu, the message is emitted with the position and size of the
+    /// menu. The application then opens the popup with
+    /// [`command::context_menu_popup`](crate::command::context_menu_popup), using an ID from
+    /// [`Core::next_surface_id`](crate::app::Core::next_surface_id), and shows the items
+    /// in its view of the popup with [`menu::popup`](super::popup).
+    #[cfg(feature = "wayland")]
+    #[must_use]
+    pub fn on_popup(mut self, on_popup: impl Fn(ContextMenuPopup) -> Message + 'a) -> Self {
+        self.on_popup = Some(Box::new(on_popup));
+        self
+    }
+
+    /// Opens the menu at `position`.
+    #[cfg_attr(not(feature = "wayland"), allow(unused_variables))]
+    fn open(
+        &self,
+        state: &mut State,
+        position: Point,
+        highlight: bool,
+        renderer: &Renderer,
+        shell: &mut Shell<'_, Message>,
+    ) {
+        #[cfg(feature = "wayland")]
+        if let Some(on_popup) = self.on_popup.as_ref() {
+            let size = super::overlay::popup_size(renderer, &self.menu.items, &self.metrics);
+            shell.publish(on_popup(ContextMenuPopup { position, size }));
+            return;
+        }
+
+        state.position = position;
+        state.menu.open(0, &self.menu.items, highlight);
+    }
+
+    /// Whether the content or one of its widgets has focus.
+    fn has_focus(&self, tree: &mut Tree, layout: Layout<'_>, renderer: &Renderer) -> bool {
+        struct Focused(bool);
+
+        impl<T> Operation<T> for Focused {
+            fn focusable(&mut self, state: &mut dyn operation::Focusable, _id: Option<&Id>) {
+                self.0 |= state.is_focused();
+            }
+
+            fn container(
+                &mut self,
+                _id: Option<&Id>,
+                _bounds: Rectangle,
+                operate_on_children: &mut dyn FnMut(&mut dyn Operation<T>),
+            ) {
+                operate_on_children(self);
+            }
+        }
+
+        let mut focused = Focused(false);
+        self.content
+            .as_widget()
+            .operate(tree, layout, renderer, &mut focused);
+        focused.0
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    menu: MenuState,
+    /// Where the menu was opened.
+    position: Point,
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for ContextMenu<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&mut self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_mut(&mut self.content));
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.content.as_widget().layout(renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<OperationOutputWrapper<Message>>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        // nested context menus and the content itself take precedence
+        if let event::Status::Captured = self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event.clone(),
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        ) {
+            return event::Status::Captured;
+        }
+
+        let bounds = layout.bounds();
+        let pointer = cursor_position.position_over(bounds);
+
+        let (position, highlight) = match event {
+            Event::Mouse(mouse::Event::ButtonPressed(mouse::Button::Right)) => match pointer {
+                Some(position) => (position, false),
+                None => return event::Status::Ignored,
+            },
+
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                modifiers,
+            }) if (key_code == KeyCode::Apps && modifiers.is_empty())
+                || (key_code == KeyCode::F10 && modifiers == keyboard::Modifiers::SHIFT) =>
+            {
+                // the menu of the focused content opens at the pointer if it is over the content
+                match pointer {
+                    _ if self.has_focus(&mut tree.children[0], layout, renderer) => {
+                        (pointer.unwrap_or(bounds.position()), true)
+                    }
+                    Some(position) => (position, true),
+                    None => return event::Status::Ignored,
+                }
+            }
+
+            _ => return event::Status::Ignored,
+        };
+
+        let state = tree.state.downcast_mut::<State>();
+        self.open(state, position, highlight, renderer, shell);
+
+        event::Status::Captured
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor_position,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        let state = tree.state.downcast_mut::<State>();
+        state.menu.clamp(std::slice::from_ref(&self.menu));
+
+        if !state.menu.is_open() {
+            return self
+                .content
+                .as_widget_mut()
+                .overlay(&mut tree.children[0], layout, renderer);
+        }
+
+        let overlay = MenuOverlay {
+            state: &mut state.menu,
+            menus: std::slice::from_ref(&self.menu),
+            anchor: Size::ZERO,
+            passthrough: Rectangle::default(),
+            switch_menus: false,
+            metrics: self.metrics,
+        };
+
+        Some(overlay.element(state.position))
+    }
+}
+
+impl<'a, Message: Clone + 'a> From<ContextMenu<'a, Message>> for Element<'a, Message> {
+    fn from(context_menu: ContextMenu<'a, Message>) -> Self {
+        Self::new(context_menu)
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Menus of buttons, check and radio items, separators and submenus.
+//!
+//! Menus are shown by a [`MenuBar`], which is typically placed in the start of the header bar:
+//!
+//! ```ignore
+//! fn header_start(&self) -> Vec<Element<Self::Message>> {
+//!     vec![menu_bar(vec![
+//!         Menu::new("_File", vec![
+//!             menu::action(&self.shortcuts, "new-window", "_New Window"),
+//!             menu::separator(),
+//!             menu::button("_Quit", Message::Quit),
+//!         ]),
+//!         Menu::new("_View", vec![
+//!             menu::check("Show _Hidden Files", self.show_hidden, Message::ToggleHidden),
+//!             menu::submenu("_Sort By", vec![
+//!                 menu::radio("_Name", self.sort == Sort::Name, Message::Sort(Sort::Name)),
+//!                 menu::radio("_Size", self.sort == Sort::Size, Message::Sort(Sort::Size)),
+//!             ]),
+//!         ]),
+//!     ])
+//!     .into()]
+//! }
+//! ```
+//!
+//! Any element may also open a menu at the pointer when it is right-clicked, by wrapping it in a
+//! [`context_menu`].
+//!
+//! An underscore in a label marks the following character as its mnemonic, which activates the
+//! item when its key is pressed while the menu is open, or opens the menu of a menu bar when the
+//! key is pressed while holding Alt.
+
+mod bar;
+mod context;
+pub(crate) mod overlay;
+#[cfg(feature = "wayland")]
+mod popup;
+
+pub use bar::{menu_bar, MenuBar};
+#[cfg(feature = "wayland")]
+pub use context::ContextMenuPopup;
+pub use context::{context_menu, ContextMenu};
+#[cfg(feature = "wayland")]
+pub use popup::{popup, MenuPopup};
+
+use crate::shortcuts::Shortcuts;
+use std::borrow::Cow;
+
+/// A menu of items, which is opened from its label.
+pub struct Menu<'a, Message> {
+    pub(crate) label: Label<'a>,
+    pub(crate) items: Vec<Item<'a, Message>>,
+}
+
+impl<'a, Message> Menu<'a, Message> {
+    /// A menu which is opened from `label`.
+    pub fn new(label: impl Into<Cow<'a, str>>, items: Vec<Item<'a, Message>>) -> Self {
+        Self {
+            label: Label::new(label.into()),
+            items,
+        }
+    }
+}
+
+/// An item of a [`Menu`].
+pub enum Item<'a, Message> {
+    /// Emits a message when activated.
+    Button(Entry<'a, Message>),
+    /// Shows a check mark when checked.
+    Check(Entry<'a, Message>, bool),
+    /// Shows a dot when it is the selected option of a group.
+    Radio(Entry<'a, Message>, bool),
+    /// Opens another menu beside this one.
+    Submenu(Label<'a>, Vec<Item<'a, Message>>),
+    /// Separates groups of items.
+    Separator,
+}
+
+/// The label, accelerator and message of an activatable [`Item`].
+pub struct Entry<'a, Message> {
+    pub(crate) label: Label<'a>,
+    pub(crate) accelerator: Option<Cow<'a, str>>,
+    pub(crate) on_activate: Option<Message>,
+}
+
+impl<'a, Message> Item<'a, Message> {
+    /// Shows the key binding which also activates this item, such as `Ctrl+Q`.
+    #[must_use]
+    pub fn accelerator(mut self, accelerator: impl Into<Cow<'a, str>>) -> Self {
+        if let Some(entry) = self.entry_mut() {
+            entry.accelerator = Some(accelerator.into());
+        }
+        self
+    }
+
+    /// Disables the item, so that it can not be activated.
+    #[must_use]
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        if let Some(entry) = self.entry_mut().filter(|_| !enabled) {
+            entry.on_activate = None;
+        }
+        self
+    }
+
+    /// The label of the item, if it has one.
+    pub(crate) fn label(&self) -> Option<&Label<'a>> {
+        match self {
+            Self::Button(entry) | Self::Check(entry, _) | Self::Radio(entry, _) => {
+                Some(&entry.label)
+            }
+            Self::Submenu(label, _) => Some(label),
+            Self::Separator => None,
+        }
+    }
+
+    /// Whether the item can be highlighted and activated.
+    pub(crate) fn is_enabled(&self) -> bool {
+        match self {
+            Self::Button(entry) | Self::Check(entry, _) | Self::Radio(entry, _) => {
+                entry.on_activate.is_some()
+            }
+            Self::Submenu(_, items) => !items.is_empty(),
+            Self::Separator => false,
+        }
+    }
+
+    fn entry_mut(&mut self) -> Option<&mut Entry<'a, Message>> {
+        match self {
+            Self::Button(entry) | Self::Check(entry, _) | Self::Radio(entry, _) => Some(entry),
+            Self::Submenu(..) | Self::Separator => None,
+        }
+    }
+}
+
+/// Text of a menu or item, with an optional mnemonic.
+pub struct Label<'a> {
+    pub(crate) text: Cow<'a, str>,
+    /// Byte offset and lowercase character of the mnemonic.
+    pub(crate) mnemonic: Option<(usize, char)>,
+}
+
+impl<'a> Label<'a> {
+    /// Parses a label where an underscore marks the mnemonic, and two underscores are shown
+    /// as one.
+    pub fn new(label: Cow<'a, str>) -> Self {
+        if !label.contains('_') {
+            return Self {
+                text: label,
+                mnemonic: None,
+            };
+        }
+
+        let mut text = String::with_capacity(label.len());
+        let mut mnemonic = None;
+        let mut chars = label.chars();
+        while let Some(c) = chars.next() {
+            if c != '_' {
+                text.push(c);
+                continue;
+            }
+
+            match chars.next() {
+                Some('_') => text.push('_'),
+                Some(c) => {
+                    if mnemonic.is_none() {
+                        mnemonic = c.to_lowercase().next().map(|m| (text.len(), m));
+                    }
+                    text.push(c);
+                }
+                None => (),
+            }
+        }
+
+        Self {
+            text: Cow::Owned(text),
+            mnemonic,
+        }
+    }
+}
+
+fn entry<'a, Message>(label: impl Into<Cow<'a, str>>, message: Message) -> Entry<'a, Message> {
+    Entry {
+        label: Label::new(label.into()),
+        accelerator: None,
+        on_activate: Some(message),
+    }
+}
+
+/// A button which emits `message` when activated.
+pub fn button<'a, Message>(label: impl Into<Cow<'a, str>>, message: Message) -> Item<'a, Message> {
+    Item::Button(entry(label, message))
+}
+
+/// A button which triggers an action of a shortcut registry, showing its key binding.
+///
+/// The item is disabled if the registry has no action named `name`.
+pub fn action<'a, Message: Clone>(
+    shortcuts: &Shortcuts<Message>,
+    name: &str,
+    label: impl Into<Cow<'a, str>>,
+) -> Item<'a, Message> {
+    let mut entry = Entry {
+        label: Label::new(label.into()),
+        accelerator: shortcuts.label(name).map(Cow::Owned),
+        on_activate: None,
+    };
+
+    entry.on_activate = shortcuts
+        .iter()
+        .find(|(action, ..)| *action == name)
+        .map(|(_, message, _)| message.clone());
+
+    Item::Button(entry)
+}
+
+/// An item which shows a check mark when `checked`, and emits `message` when activated.
+pub fn check<'a, Message>(
+    label: impl Into<Cow<'a, str>>,
+    checked: bool,
+    message: Message,
+) -> Item<'a, Message> {
+    Item::Check(entry(label, message), checked)
+}
+
+/// An option of a group which shows a dot when `selected`, and emits `message` when activated.
+pub fn radio<'a, Message>(
+    label: impl Into<Cow<'a, str>>,
+    selected: bool,
+    message: Message,
+) -> Item<'a, Message> {
+    Item::Radio(entry(label, message), selected)
+}
+
+/// An item which opens a menu of `items` beside its menu.
+pub fn submenu<'a, Message>(
+    label: impl Into<Cow<'a, str>>,
+    items: Vec<Item<'a, Message>>,
+) -> Item<'a, Message> {
+    Item::Submenu(Label::new(label.into()), items)
+}
+
+/// A line which separates groups of items.
+#[must_use]
+pub fn separator<'a, Message>() -> Item<'a, Message> {
+    Item::Separator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Label;
+
+    fn parse(label: &str) -> (String, Option<(usize, char)>) {
+        let label = Label::new(label.into());
+        (label.text.into_owned(), label.mnemonic)
+    }
+
+    #[test]
+    fn test_label_without_mnemonic() {
+        assert_eq!(parse("Quit"), ("Quit".to_string(), None));
+        assert_eq!(parse(""), (String::new(), None));
+    }
+
+    #[test]
+    fn test_label_mnemonic() {
+        assert_eq!(parse("_Quit"), ("Quit".to_string(), Some((0, 'q'))));
+        assert_eq!(parse("Save _As"), ("Save As".to_string(), Some((5, 'a'))));
+        // only the first mnemonic is used
+        assert_eq!(parse("_Save _As"), ("Save As".to_string(), Some((0, 's'))));
+    }
+
+    #[test]
+    fn test_label_escaped_underscore() {
+        assert_eq!(parse("snake__case"), ("snake_case".to_string(), None));
+        assert_eq!(parse("__init_"), ("_init".to_string(), None));
+        assert_eq!(parse("a___b"), ("a_b".to_string(), Some((2, 'b'))));
+    }
+
+    #[test]
+    fn test_label_trailing_underscore() {
+        assert_eq!(parse("Quit_"), ("Quit".to_string(), None));
+        assert_eq!(parse("_"), (String::new(), None));
+    }
+
+    #[test]
+    fn test_label_non_ascii() {
+        // the offset is in bytes, so that the mnemonic can be found in the text
+        assert_eq!(parse("Ü_ber"), ("Über".to_string(), Some((2, 'b'))));
+        assert_eq!(parse("_Ärger"), ("Ärger".to_string(), Some((0, 'ä'))));
+        assert_eq!(parse("日本_語"), ("日本語".to_string(), Some((6, '語'))));
+    }
+}
@@ -0,0 +1,991 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Shows an open menu and its open submenus above the rest of the window.
+
+use super::{Item, Label, Menu};
+use crate::theme::elevation::{self, ElevationLevel, StyleSheet as _};
+use crate::theme::{Theme, THEME};
+use crate::widget::icon::{self, IconSource};
+use crate::Renderer;
+use iced_core::event::{self, Event};
+use iced_core::keyboard::{self, KeyCode};
+use iced_core::text::{self, LineHeight, Shaping};
+use iced_core::{alignment, layout, mouse, overlay, renderer, touch};
+use iced_core::{
+    Background, BorderRadius, Clipboard, Color, Layout, Point, Rectangle, Shell, Size,
+};
+
+/// Dimensions of menus, which are read from the theme when a menu is created.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Metrics {
+    pub(crate) item_height: f32,
+    pub(crate) item_padding: f32,
+    pub(crate) menu_padding: f32,
+    pub(crate) separator_height: f32,
+    pub(crate) icon_size: u16,
+    pub(crate) min_width: f32,
+    pub(crate) radius: [f32; 4],
+}
+
+impl Metrics {
+    pub(crate) fn from_theme() -> Self {
+        THEME.with(|theme| {
+            let theme = theme.borrow();
+            let cosmic = theme.cosmic();
+
+            Self {
+                item_height: 32.0,
+                item_padding: f32::from(cosmic.spacing.space_s),
+                menu_padding: f32::from(cosmic.spacing.space_xxxs),
+                separator_height: f32::from(cosmic.spacing.space_xxs) + 1.0,
+                icon_size: 16,
+                min_width: 160.0,
+                radius: cosmic.corner_radii.radius_s,
+            }
+        })
+    }
+}
+
+/// Which menu is open, and which of its items are highlighted.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MenuState {
+    /// Index of the open menu.
+    pub(crate) open: Option<usize>,
+    /// Highlighted item of each open menu, from the first menu to the deepest submenu.
+    pub(crate) levels: Vec<Option<usize>>,
+}
+
+impl MenuState {
+    /// Opens a menu, highlighting its first item when it was opened with the keyboard.
+    pub(crate) fn open<Message>(
+        &mut self,
+        index: usize,
+        items: &[Item<'_, Message>],
+        highlight: bool,
+    ) {
+        self.open = Some(index);
+        self.levels = vec![if highlight {
+            next_enabled(items, None, true)
+        } else {
+            None
+        }];
+    }
+
+    /// Closes the menu and its submenus.
+    pub(crate) fn close(&mut self) {
+        self.open = None;
+        self.levels.clear();
+    }
+
+    /// Whether a menu is open.
+    pub(crate) fn is_open(&self) -> bool {
+        self.open.is_some()
+    }
+
+    /// Closes the menu if it no longer exists, and drops the submenus whose items no longer
+    /// exist, since the menus may have changed since the state was last updated.
+    pub(crate) fn clamp<Message>(&mut self, menus: &[Menu<'_, Message>]) {
+        let Some(menu) = self.open.and_then(|open| menus.get(open)) else {
+            self.close();
+            return;
+        };
+
+        let mut items = menu.items.as_slice();
+        for level in 0..self.levels.len() {
+            match self.levels[level].map(|index| items.get(index)) {
+                Some(Some(Item::Submenu(_, children))) => items = children,
+                Some(None) => {
+                    self.levels[level] = None;
+                    self.levels.truncate(level + 1);
+                    return;
+                }
+                _ => {
+                    self.levels.truncate(level + 1);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// The overlay of an open menu.
+pub(crate) struct MenuOverlay<'a, 'b, Message> {
+    pub(crate) state: &'a mut MenuState,
+    pub(crate) menus: &'a [Menu<'b, Message>],
+    /// Size of the area which opened the menu, at the position of the overlay.
+    pub(crate) anchor: Size,
+    /// Area where presses are left to the widget which opened the menu, such as a menu bar.
+    pub(crate) passthrough: Rectangle,
+    /// Whether Left and Right switch between the menus of a menu bar.
+    pub(crate) switch_menus: bool,
+    pub(crate) metrics: Metrics,
+}
+
+impl<'a, 'b, Message: Clone> MenuOverlay<'a, 'b, Message> {
+    pub(crate) fn element(self, position: Point) -> overlay::Element<'a, Message, Renderer> {
+        overlay::Element::new(position, Box::new(self))
+    }
+}
+
+impl<'a, 'b, Message> MenuOverlay<'a, 'b, Message> {
+    /// The items of the menu which is open at a level.
+    fn level_items(&self, level: usize) -> Option<&'a [Item<'b, Message>]> {
+        let menus: &'a [Menu<'b, Message>] = self.menus;
+        let mut items = menus.get(self.state.open?)?.items.as_slice();
+
+        for highlighted in self.state.levels.get(..level)? {
+            match items.get((*highlighted)?)? {
+                Item::Submenu(_, children) => items = children,
+                _ => return None,
+            }
+        }
+
+        Some(items)
+    }
+
+    /// The level and item under the cursor, searching from the deepest submenu.
+    fn hit(&self, layout: Layout<'_>, cursor: mouse::Cursor) -> Option<(usize, Option<usize>)> {
+        let position = cursor.position()?;
+        let menus: Vec<_> = layout.children().enumerate().collect();
+
+        menus.into_iter().rev().find_map(|(level, menu)| {
+            let bounds = menu.bounds();
+            if !bounds.contains(position) {
+                return None;
+            }
+
+            let items = self.level_items(level)?;
+            let index = item_bounds(bounds, items, &self.metrics)
+                .iter()
+                .position(|item| item.contains(position));
+
+            Some((level, index))
+        })
+    }
+
+    /// Highlights an item under the cursor, opening it if it is a submenu.
+    fn highlight(&mut self, level: usize, index: Option<usize>) {
+        let Some(items) = self.level_items(level) else {
+            return;
+        };
+
+        let index = index.filter(|&index| items.get(index).map_or(false, Item::is_enabled));
+        match self.state.levels.get(level) {
+            Some(highlighted) if *highlighted != index => (),
+            _ => return,
+        }
+
+        self.state.levels.truncate(level + 1);
+        self.state.levels[level] = index;
+        if let Some(Item::Submenu(..)) = index.and_then(|index| items.get(index)) {
+            self.state.levels.push(None);
+        }
+    }
+
+    /// Emits the message of an item and closes the menu, or opens a submenu.
+    fn activate(&mut self, level: usize, index: usize, shell: &mut Shell<'_, Message>)
+    where
+        Message: Clone,
+    {
+        let Some(item) = self.level_items(level).and_then(|items| items.get(index)) else {
+            return;
+        };
+        if level >= self.state.levels.len() {
+            return;
+        }
+
+        match item {
+            Item::Button(entry) | Item::Check(entry, _) | Item::Radio(entry, _) => {
+                if let Some(message) = entry.on_activate.as_ref() {
+                    shell.publish(message.clone());
+                    self.state.close();
+                }
+            }
+
+            Item::Submenu(_, children) => {
+                self.state.levels.truncate(level + 1);
+                self.state.levels[level] = Some(index);
+                self.state.levels.push(next_enabled(children, None, true));
+            }
+
+            Item::Separator => (),
+        }
+    }
+
+    /// Opens the previous or next menu of a menu bar.
+    fn step_menu(&mut self, forward: bool) {
+        let (Some(open), true) = (self.state.open, self.switch_menus) else {
+            return;
+        };
+
+        let len = self.menus.len();
+        if len == 0 {
+            self.state.close();
+            return;
+        }
+
+        let next = if forward {
+            (open + 1) % len
+        } else {
+            (open + len - 1) % len
+        };
+
+        if let Some(menu) = self.menus.get(next) {
+            self.state.open(next, &menu.items, true);
+        }
+    }
+
+    fn on_key(&mut self, key_code: KeyCode, shell: &mut Shell<'_, Message>)
+    where
+        Message: Clone,
+    {
+        if self.state.levels.is_empty() {
+            self.state.levels.push(None);
+        }
+
+        let level = self.state.levels.len() - 1;
+        let Some(items) = self.level_items(level) else {
+            self.state.close();
+            return;
+        };
+        let highlighted = self.state.levels[level];
+
+        match key_code {
+            KeyCode::Escape => {
+                if level > 0 {
+                    self.state.levels.pop();
+                } else {
+                    self.state.close();
+                }
+            }
+
+            KeyCode::Up => self.state.levels[level] = next_enabled(items, highlighted, false),
+            KeyCode::Down => self.state.levels[level] = next_enabled(items, highlighted, true),
+            KeyCode::Home => self.state.levels[level] = next_enabled(items, None, true),
+            KeyCode::End => self.state.levels[level] = next_enabled(items, None, false),
+
+            KeyCode::Right => match highlighted {
+                Some(index) if matches!(items.get(index), Some(Item::Submenu(..))) => {
+                    self.activate(level, index, shell);
+                }
+                _ => self.step_menu(true),
+            },
+
+            KeyCode::Left => {
+                if level > 0 {
+                    self.state.levels.pop();
+                } else {
+                    self.step_menu(false);
+                }
+            }
+
+            KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space => {
+                if let Some(index) = highlighted {
+                    self.activate(level, index, shell);
+                }
+            }
+
+            key_code => {
+                let Some(mnemonic) = mnemonic_char(key_code) else {
+                    return;
+                };
+
+                let index = items.iter().position(|item| {
+                    item.is_enabled()
+                        && item
+                            .label()
+                            .and_then(|label| label.mnemonic)
+                            .map(|(_, c)| c)
+                            == Some(mnemonic)
+                });
+
+                if let Some(index) = index {
+                    self.activate(level, index, shell);
+                }
+            }
+        }
+    }
+
+    fn draw_item(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        item: &Item<'b, Message>,
+        bounds: Rectangle,
+        highlighted: bool,
+        indicator: f32,
+    ) {
+        let cosmic = theme.cosmic();
+        let metrics = &self.metrics;
+
+        if let Item::Separator = item {
+            let divider = Rectangle {
+                x: bounds.x + metrics.item_padding / 2.0,
+                y: bounds.center_y().floor(),
+                width: bounds.width - metrics.item_padding,
+                height: 1.0,
+            };
+            fill(
+                renderer,
+                divider,
+                [0.0; 4],
+                cosmic.background.divider.into(),
+            );
+            return;
+        }
+
+        if highlighted {
+            let hover = cosmic.background.component.hover.into();
+            fill(renderer, bounds, metrics.radius, hover);
+        }
+
+        let color: Color = if item.is_enabled() {
+            cosmic.background.on.into()
+        } else {
+            cosmic.background.component.on_disabled.into()
+        };
+
+        let x = bounds.x + metrics.item_padding;
+        let icon_size = f32::from(metrics.icon_size);
+        let icon_bounds = Rectangle {
+            x,
+            y: bounds.center_y() - icon_size / 2.0,
+            width: icon_size,
+            height: icon_size,
+        };
+
+        match item {
+            Item::Check(_, true) => {
+                draw_icon(
+                    renderer,
+                    "object-select-symbolic",
+                    metrics.icon_size,
+                    color,
+                    icon_bounds,
+                );
+            }
+
+            Item::Radio(_, true) => {
+                let dot = icon_size / 2.0;
+                let dot_bounds = Rectangle {
+                    x: icon_bounds.center_x() - dot / 2.0,
+                    y: icon_bounds.center_y() - dot / 2.0,
+                    width: dot,
+                    height: dot,
+                };
+                fill(renderer, dot_bounds, [dot / 2.0; 4], color);
+            }
+
+            _ => (),
+        }
+
+        if let Some(label) = item.label() {
+            draw_label(
+                renderer,
+                label,
+                Point::new(x + indicator, bounds.center_y()),
+                color,
+                true,
+            );
+        }
+
+        let end = bounds.x + bounds.width - metrics.item_padding;
+        match item {
+            Item::Button(entry) | Item::Check(entry, _) | Item::Radio(entry, _) => {
+                if let Some(accelerator) = entry.accelerator.as_deref() {
+                    renderer.fill_text(text::Text {
+                        content: accelerator,
+                        bounds: Rectangle {
+                            x: end,
+                            y: bounds.center_y(),
+                            ..bounds
+                        },
+                        size: text::Renderer::default_size(renderer),
+                        line_height: LineHeight::default(),
+                        color: Color {
+                            a: color.a * 0.7,
+                            ..color
+                        },
+                        font: text::Renderer::default_font(renderer),
+                        horizontal_alignment: alignment::Horizontal::Right,
+                        vertical_alignment: alignment::Vertical::Center,
+                        shaping: Shaping::Advanced,
+                    });
+                }
+            }
+
+            Item::Submenu(..) => {
+                let arrow_bounds = Rectangle {
+                    x: end - icon_size,
+                    ..icon_bounds
+                };
+                draw_icon(
+                    renderer,
+                    "go-next-symbolic",
+                    metrics.icon_size,
+                    color,
+                    arrow_bounds,
+                );
+            }
+
+            Item::Separator => (),
+        }
+    }
+}
+
+impl<'a, 'b, Message: Clone> overlay::Overlay<Message, Renderer> for MenuOverlay<'a, 'b, Message> {
+    fn layout(&self, renderer: &Renderer, bounds: Size, position: Point) -> layout::Node {
+        let mut children = Vec::new();
+        // bounds of the previous menu, and of its item which opened the next menu
+        let mut parent: Option<(Rectangle, Rectangle)> = None;
+
+        for level in 0..self.state.levels.len() {
+            let Some(items) = self.level_items(level) else {
+                break;
+            };
+
+            let size = menu_size(renderer, items, &self.metrics);
+
+            // the first menu drops down from the anchor, and submenus open beside their item
+            let (mut x, mut y) = match parent {
+                None => (position.x, position.y + self.anchor.height),
+                Some((menu, item)) => (menu.x + menu.width, item.y - self.metrics.menu_padding),
+            };
+
+            if x + size.width > bounds.width {
+                x = match parent {
+                    Some((menu, _)) => menu.x - size.width,
+                    None => bounds.width - size.width,
+                };
+            }
+
+            if y + size.height > bounds.height {
+                y = if parent.is_none() && position.y >= size.height {
+                    position.y - size.height
+                } else {
+                    bounds.height - size.height
+                };
+            }
+
+            let menu = Rectangle::new(Point::new(x.max(0.0), y.max(0.0)), size);
+            let mut node = layout::Node::new(size);
+            node.move_to(menu.position());
+            children.push(node);
+
+            let highlighted = self.state.levels[level]
+                .and_then(|index| item_bounds(menu, items, &self.metrics).get(index).copied());
+            parent = match highlighted {
+                Some(item) => Some((menu, item)),
+                None => break,
+            };
+        }
+
+        layout::Node::with_children(bounds, children)
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        self.state.clamp(self.menus);
+
+        match event {
+            Event::Mouse(mouse::Event::CursorMoved { .. })
+            | Event::Touch(touch::Event::FingerMoved { .. }) => {
+                let Some((level, index)) = self.hit(layout, cursor_position) else {
+                    return event::Status::Ignored;
+                };
+
+                self.highlight(level, index);
+            }
+
+            Event::Mouse(mouse::Event::ButtonPressed(_))
+            | Event::Touch(touch::Event::FingerPressed { .. }) => {
+                if self.hit(layout, cursor_position).is_none() {
+                    if cursor_position.is_over(self.passthrough) {
+                        return event::Status::Ignored;
+                    }
+
+                    self.state.close();
+                }
+            }
+
+            Event::Mouse(mouse::Event::ButtonReleased(mouse::Button::Left))
+            | Event::Touch(touch::Event::FingerLifted { .. }) => {
+                match self.hit(layout, cursor_position) {
+                    Some((level, Some(index))) => self.activate(level, index, shell),
+                    Some((_, None)) => (),
+                    None => return event::Status::Ignored,
+                }
+            }
+
+            // keys are captured while the menu is open so that they do not trigger shortcuts
+            Event::Keyboard(keyboard::Event::KeyPressed { key_code, .. }) => {
+                self.on_key(key_code, shell);
+            }
+
+            _ => return event::Status::Ignored,
+        }
+
+        event::Status::Captured
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        _viewport: &Rectangle,
+        _renderer: &Renderer,
+    ) -> mouse::Interaction {
+        match self.hit(layout, cursor_position) {
+            Some((level, Some(index)))
+                if self
+                    .level_items(level)
+                    .and_then(|items| items.get(index))
+                    .map_or(false, Item::is_enabled) =>
+            {
+                mouse::Interaction::Pointer
+            }
+            _ => mouse::Interaction::Idle,
+        }
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        _cursor_position: mouse::Cursor,
+    ) {
+        let elevation = theme.elevation(ElevationLevel::Overlay);
+        let background = theme.cosmic().background.base.into();
+
+        for (level, menu) in layout.children().enumerate() {
+            let Some(items) = self.level_items(level) else {
+                break;
+            };
+
+            let bounds = menu.bounds();
+            elevation::draw_shadow(renderer, bounds, &elevation);
+            fill(renderer, bounds, self.metrics.radius, background);
+
+            let indicator = indicator_width(items, &self.metrics);
+            let highlighted = self.state.levels.get(level).copied().flatten();
+            let item_bounds = item_bounds(bounds, items, &self.metrics);
+
+            for (index, (item, item_bounds)) in items.iter().zip(item_bounds).enumerate() {
+                let is_highlighted = highlighted == Some(index);
+                self.draw_item(
+                    renderer,
+                    theme,
+                    item,
+                    item_bounds,
+                    is_highlighted,
+                    indicator,
+                );
+            }
+
+            elevation::draw_outline(renderer, bounds, &elevation);
+        }
+    }
+
+    fn is_over(&self, layout: Layout<'_>, _renderer: &Renderer, cursor_position: Point) -> bool {
+        layout
+            .children()
+            .any(|menu| menu.bounds().contains(cursor_position))
+    }
+}
+
+/// The next item after `from` which can be highlighted, wrapping around the menu.
+pub(crate) fn next_enabled<Message>(
+    items: &[Item<'_, Message>],
+    from: Option<usize>,
+    forward: bool,
+) -> Option<usize> {
+    let len = items.len();
+    let start = match (from, forward) {
+        (Some(index), true) => index + 1,
+        (Some(index), false) => index + len - 1,
+        (None, true) => 0,
+        (None, false) => len.checked_sub(1)?,
+    };
+
+    (0..len)
+        .map(|n| {
+            if forward {
+                (start + n) % len
+            } else {
+                (start + len - n) % len
+            }
+        })
+        .find(|&index| items[index].is_enabled())
+}
+
+/// The lowercase character which a key types, for matching mnemonics.
+pub(crate) fn mnemonic_char(key_code: KeyCode) -> Option<char> {
+    let name = crate::shortcuts::key_name(key_code)?;
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_alphanumeric() => Some(c.to_ascii_lowercase()),
+        _ => None,
+    }
+}
+
+/// Draws a label whose left edge and vertical center are at `position`, underlining its
+/// mnemonic if `show_mnemonic` is set.
+pub(crate) fn draw_label(
+    renderer: &mut Renderer,
+    label: &Label<'_>,
+    position: Point,
+    color: Color,
+    show_mnemonic: bool,
+) {
+    let size = text::Renderer::default_size(renderer);
+
+    renderer.fill_text(text::Text {
+        content: &label.text,
+        bounds: Rectangle::new(position, Size::INFINITY),
+        size,
+        line_height: LineHeight::default(),
+        color,
+        font: text::Renderer::default_font(renderer),
+        horizontal_alignment: alignment::Horizontal::Left,
+        vertical_alignment: alignment::Vertical::Center,
+        shaping: Shaping::Advanced,
+    });
+
+    let Some((offset, _)) = label.mnemonic.filter(|_| show_mnemonic) else {
+        return;
+    };
+
+    let end = label.text[offset..]
+        .chars()
+        .next()
+        .map_or(offset, |c| offset + c.len_utf8());
+
+    let underline = Rectangle {
+        x: position.x + measure(renderer, &label.text[..offset]),
+        y: (position.y + size * 0.6).round(),
+        width: measure(renderer, &label.text[offset..end]),
+        height: 1.0,
+    };
+
+    fill(renderer, underline, [0.0; 4], color);
+}
+
+/// The size of a menu together with its submenus, as they open beside their items, which a
+/// popup surface needs to show all of them.
+#[cfg(feature = "wayland")]
+pub(crate) fn popup_size<Message>(
+    renderer: &Renderer,
+    items: &[Item<'_, Message>],
+    metrics: &Metrics,
+) -> Size {
+    let size = menu_size(renderer, items, metrics);
+    let menu = Rectangle::new(Point::ORIGIN, size);
+
+    items
+        .iter()
+        .zip(item_bounds(menu, items, metrics))
+        .fold(size, |total, (item, bounds)| match item {
+            Item::Submenu(_, children) => {
+                let submenu = popup_size(renderer, children, metrics);
+                Size::new(
+                    total.width.max(size.width + submenu.width),
+                    total
+                        .height
+                        .max(bounds.y - metrics.menu_padding + submenu.height),
+                )
+            }
+            _ => total,
+        })
+}
+
+/// The size of a menu, without its submenus.
+pub(crate) fn menu_size<Message>(
+    renderer: &Renderer,
+    items: &[Item<'_, Message>],
+    metrics: &Metrics,
+) -> Size {
+    let mut width = 0.0f32;
+    let mut height = 0.0;
+
+    for item in items {
+        height += item_height(item, metrics);
+
+        let Some(label) = item.label() else {
+            continue;
+        };
+
+        let mut item_width = measure(renderer, &label.text);
+        match item {
+            Item::Button(entry) | Item::Check(entry, _) | Item::Radio(entry, _) => {
+                if let Some(accelerator) = entry.accelerator.as_deref() {
+                    item_width += metrics.item_padding * 2.0 + measure(renderer, accelerator);
+                }
+            }
+            Item::Submenu(..) => {
+                item_width += metrics.item_padding + f32::from(metrics.icon_size);
+            }
+            Item::Separator => (),
+        }
+
+        width = width.max(item_width);
+    }
+
+    width += indicator_width(items, metrics) + metrics.item_padding * 2.0;
+
+    Size::new(
+        (width + metrics.menu_padding * 2.0).max(metrics.min_width),
+        height + metrics.menu_padding * 2.0,
+    )
+}
+
+/// The width of text drawn with the default font and size.
+pub(crate) fn measure(renderer: &Renderer, content: &str) -> f32 {
+    text::Renderer::measure(
+        renderer,
+        content,
+        text::Renderer::default_size(renderer),
+        LineHeight::default(),
+        text::Renderer::default_font(renderer),
+        Size::INFINITY,
+        Shaping::Advanced,
+    )
+    .width
+}
+
+pub(crate) fn fill(renderer: &mut Renderer, bounds: Rectangle, radius: [f32; 4], color: Color) {
+    renderer::Renderer::fill_quad(
+        renderer,
+        renderer::Quad {
+            bounds,
+            border_radius: BorderRadius::from(radius),
+            border_width: 0.0,
+            border_color: Color::TRANSPARENT,
+        },
+        Background::Color(color),
+    );
+}
+
+fn draw_icon(renderer: &mut Renderer, name: &str, size: u16, color: Color, bounds: Rectangle) {
+    if let icon::Handle::Svg(handle) = IconSource::from(name).load(size, None, true, true) {
+        iced_core::svg::Renderer::draw(renderer, handle, Some(color), bounds);
+    }
+}
+
+fn item_height<Message>(item: &Item<'_, Message>, metrics: &Metrics) -> f32 {
+    match item {
+        Item::Separator => metrics.separator_height,
+        _ => metrics.item_height,
+    }
+}
+
+/// Width of the column of check marks and radio dots, if the menu has any.
+fn indicator_width<Message>(items: &[Item<'_, Message>], metrics: &Metrics) -> f32 {
+    if items
+        .iter()
+        .any(|item| matches!(item, Item::Check(..) | Item::Radio(..)))
+    {
+        f32::from(metrics.icon_size) + metrics.item_padding / 2.0
+    } else {
+        0.0
+    }
+}
+
+/// The bounds of each item of a menu.
+fn item_bounds<Message>(
+    menu: Rectangle,
+    items: &[Item<'_, Message>],
+    metrics: &Metrics,
+) -> Vec<Rectangle> {
+    let mut y = menu.y + metrics.menu_padding;
+
+    items
+        .iter()
+        .map(|item| {
+            let height = item_height(item, metrics);
+            let bounds = Rectangle {
+                x: menu.x + metrics.menu_padding,
+                y,
+                width: menu.width - metrics.menu_padding * 2.0,
+                height,
+            };
+            y += height;
+            bounds
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{next_enabled, MenuOverlay, MenuState, Metrics};
+    use crate::widget::menu::{button, separator, submenu, Item, Menu};
+    use iced_core::keyboard::KeyCode;
+    use iced_core::{Rectangle, Shell, Size};
+
+    fn menus() -> Vec<Menu<'static, u8>> {
+        vec![
+            Menu::new(
+                "_File",
+                vec![
+                    button("_New", 0),
+                    separator(),
+                    button("_Quit", 1).enabled(false),
+                    submenu("_Recent", vec![button("_One", 2), button("_Two", 3)]),
+                ],
+            ),
+            Menu::new("_Edit", vec![button("_Copy", 4)]),
+        ]
+    }
+
+    /// presses keys in the overlay of the menus, returning the emitted messages
+    fn press(state: &mut MenuState, menus: &[Menu<'_, u8>], keys: &[KeyCode]) -> Vec<u8> {
+        let mut messages = Vec::new();
+        let mut shell = Shell::new(&mut messages);
+        let mut overlay = MenuOverlay {
+            state,
+            menus,
+            anchor: Size::ZERO,
+            passthrough: Rectangle::default(),
+            switch_menus: true,
+            metrics: Metrics::from_theme(),
+        };
+        for &key in keys {
+            overlay.on_key(key, &mut shell);
+        }
+        messages
+    }
+
+    fn opened(menus: &[Menu<'_, u8>], index: usize) -> MenuState {
+        let mut state = MenuState::default();
+        state.open(index, &menus[index].items, true);
+        state
+    }
+
+    #[test]
+    fn test_next_enabled() {
+        let items = &menus()[0].items;
+        assert_eq!(next_enabled(items, None, true), Some(0));
+        assert_eq!(next_enabled(items, None, false), Some(3));
+        // separators and disabled items are skipped
+        assert_eq!(next_enabled(items, Some(0), true), Some(3));
+        assert_eq!(next_enabled(items, Some(3), false), Some(0));
+        // and the search wraps around
+        assert_eq!(next_enabled(items, Some(3), true), Some(0));
+        assert_eq!(next_enabled::<u8>(&[], None, true), None);
+        assert_eq!(next_enabled::<u8>(&[Item::Separator], Some(0), true), None);
+    }
+
+    #[test]
+    fn test_arrow_keys() {
+        let menus = menus();
+        let mut state = opened(&menus, 0);
+        assert_eq!(state.levels, [Some(0)]);
+
+        assert!(press(&mut state, &menus, &[KeyCode::Down]).is_empty());
+        assert_eq!(state.levels, [Some(3)]);
+        press(&mut state, &menus, &[KeyCode::Right]);
+        assert_eq!(state.levels, [Some(3), Some(0)]);
+        press(&mut state, &menus, &[KeyCode::End]);
+        assert_eq!(state.levels, [Some(3), Some(1)]);
+        press(&mut state, &menus, &[KeyCode::Left]);
+        assert_eq!(state.levels, [Some(3)]);
+
+        assert_eq!(
+            press(
+                &mut state,
+                &menus,
+                &[KeyCode::Right, KeyCode::Down, KeyCode::Enter]
+            ),
+            [3]
+        );
+        assert!(!state.is_open());
+    }
+
+    #[test]
+    fn test_switch_menus() {
+        let menus = menus();
+        let mut state = opened(&menus, 0);
+        press(&mut state, &menus, &[KeyCode::Left]);
+        assert_eq!((state.open, &*state.levels), (Some(1), &[Some(0)][..]));
+        press(&mut state, &menus, &[KeyCode::Right]);
+        assert_eq!((state.open, &*state.levels), (Some(0), &[Some(0)][..]));
+    }
+
+    #[test]
+    fn test_escape() {
+        let menus = menus();
+        let mut state = opened(&menus, 0);
+        press(&mut state, &menus, &[KeyCode::End, KeyCode::Right]);
+        assert_eq!(state.levels.len(), 2);
+        press(&mut state, &menus, &[KeyCode::Escape]);
+        assert_eq!(state.levels, [Some(3)]);
+        press(&mut state, &menus, &[KeyCode::Escape]);
+        assert!(!state.is_open());
+    }
+
+    #[test]
+    fn test_mnemonics() {
+        let menus = menus();
+        let mut state = opened(&menus, 0);
+        // disabled items are not activated
+        assert!(press(&mut state, &menus, &[KeyCode::Q]).is_empty());
+        assert_eq!(press(&mut state, &menus, &[KeyCode::R, KeyCode::T]), [3]);
+        assert!(!state.is_open());
+    }
+
+    #[test]
+    fn test_clamp() {
+        let menus = menus();
+
+        let mut state = MenuState {
+            open: Some(2),
+            levels: vec![Some(0)],
+        };
+        state.clamp(&menus);
+        assert!(!state.is_open());
+
+        // the highlighted item of a submenu no longer exists
+        let mut state = MenuState {
+            open: Some(0),
+            levels: vec![Some(3), Some(5)],
+        };
+        state.clamp(&menus);
+        assert_eq!(state.levels, [Some(3), None]);
+
+        // the submenu no longer exists
+        let mut state = MenuState {
+            open: Some(0),
+            levels: vec![Some(9), Some(0)],
+        };
+        state.clamp(&menus);
+        assert_eq!(state.levels, [None]);
+
+        // the highlighted item is no longer a submenu
+        let mut state = MenuState {
+            open: Some(0),
+            levels: vec![Some(0), Some(0)],
+        };
+        state.clamp(&menus);
+        assert_eq!(state.levels, [Some(0)]);
+    }
+
+    #[test]
+    fn test_stale_state() {
+        // keys pressed after the menus were removed close the menu instead of panicking
+        let mut state = MenuState {
+            open: Some(1),
+            levels: vec![Some(4), None],
+        };
+        assert!(press(&mut state, &[], &[KeyCode::Down]).is_empty());
+        assert!(!state.is_open());
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Shows the items of a context menu in its own popup surface.
+
+use super::overlay::{MenuOverlay, MenuState, Metrics};
+use super::{Item, Menu};
+use crate::{Element, Renderer, Theme};
+use iced_core::event::{self, Event};
+use iced_core::overlay::Overlay;
+use iced_core::widget::{tree, Tree};
+use iced_core::{layout, mouse, renderer};
+use iced_core::{Clipboard, Layout, Length, Point, Rectangle, Shell, Widget};
+
+/// Shows `items` as the content of a context menu popup.
+///
+/// Submenus are opened inside of the popup, so the popup should be given the size requested by
+/// [`ContextMenu::on_popup`](super::ContextMenu::on_popup).
+pub fn popup<'a, Message>(items: Vec<Item<'a, Message>>) -> MenuPopup<'a, Message> {
+    MenuPopup::new(items)
+}
+
+/// The content of a context menu popup.
+pub struct MenuPopup<'a, Message> {
+    menu: Menu<'a, Message>,
+    metrics: Metrics,
+    on_close: Option<Message>,
+}
+
+impl<'a, Message> MenuPopup<'a, Message> {
+    /// Shows `items` as the content of a popup.
+    pub fn new(items: Vec<Item<'a, Message>>) -> Self {
+        Self {
+            menu: Menu::new("", items),
+            metrics: Metrics::from_theme(),
+            on_close: None,
+        }
+    }
+
+    /// Emits a message when the menu closes, so that the popup may be closed with
+    /// [`command::close_popup`](crate::command::close_popup).
+    #[must_use]
+    pub fn on_close(mut self, message: Message) -> Self {
+        self.on_close = Some(message);
+        self
+    }
+
+    fn menu_overlay<'b>(&'b self, state: &'b mut MenuState) -> MenuOverlay<'b, 'a, Message> {
+        state.clamp(std::slice::from_ref(&self.menu));
+        MenuOverlay {
+            state,
+            menus: std::slice::from_ref(&self.menu),
+            anchor: iced_core::Size::ZERO,
+            passthrough: Rectangle::default(),
+            switch_menus: false,
+            metrics: self.metrics,
+        }
+    }
+}
+
+impl<'a, Message: Clone> Widget<Message, Renderer> for MenuPopup<'a, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<MenuState>()
+    }
+
+    fn state(&self) -> tree::State {
+        let mut state = MenuState::default();
+        state.open(0, &self.menu.items, false);
+        tree::State::new(state)
+    }
+
+    fn width(&self) -> Length {
+        Length::Fill
+    }
+
+    fn height(&self) -> Length {
+        Length::Fill
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        layout::Node::new(limits.max())
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        _viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<MenuState>();
+        if !state.is_open() {
+            return event::Status::Ignored;
+        }
+
+        let mut overlay = self.menu_overlay(state);
+        let node = overlay.layout(renderer, layout.bounds().size(), Point::ORIGIN);
+        let status = overlay.on_event(
+            event,
+            Layout::new(&node),
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        );
+
+        if !tree.state.downcast_ref::<MenuState>().is_open() {
+            if let Some(message) = self.on_close.clone() {
+                shell.publish(message);
+            }
+        }
+
+        status
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let mut state = tree.state.downcast_ref::<MenuState>().clone();
+        let overlay = self.menu_overlay(&mut state);
+        let node = overlay.layout(renderer, layout.bounds().size(), Point::ORIGIN);
+        overlay.mouse_interaction(Layout::new(&node), cursor_position, viewport, renderer)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        _viewport: &Rectangle,
+    ) {
+        // the overlay mutably borrows the state only while handling events
+        let mut state = tree.state.downcast_ref::<MenuState>().clone();
+        let overlay = self.menu_overlay(&mut state);
+        let node = overlay.layout(renderer, layout.bounds().size(), Point::ORIGIN);
+        overlay.draw(renderer, theme, style, Layout::new(&node), cursor_position);
+    }
+}
+
+impl<'a, Message: Clone + 'a> From<MenuPopup<'a, Message>> for Element<'a, Message> {
+    fn from(popup: MenuPopup<'a, Message>) -> Self {
+        Self::new(popup)
+    }
+}
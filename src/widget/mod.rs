@@ -21,6 +21,12 @@ pub use button::*;
 pub mod card;
 pub use card::*;
 
+pub mod context_drawer;
+pub use context_drawer::{context_drawer, ContextDrawer};
+
+pub mod dialog;
+pub use dialog::{dialog, Dialog};
+
 pub mod flex_row;
 pub use flex_row::{flex_row, FlexRow};
 
@@ -36,6 +42,9 @@ pub mod frames;
 pub mod list;
 pub use list::*;
 
+pub mod menu;
+pub use menu::{context_menu, menu_bar, ContextMenu, MenuBar};
+
 pub mod nav_bar;
 pub use nav_bar::nav_bar;
 
@@ -50,6 +59,9 @@ pub mod rectangle_tracker;
 mod scrollable;
 pub use scrollable::*;
 
+pub mod responsive;
+pub use responsive::{responsive, Responsive};
+
 pub mod search;
 
 pub mod segmented_button;
@@ -60,9 +72,12 @@ pub mod settings;
 pub mod spin_button;
 pub use spin_button::{spin_button, SpinButton};
 
-mod text;
+pub mod text;
 pub use text::{text, Text};
 
+pub mod themed;
+pub use themed::{themed, Themed};
+
 mod toggler;
 pub use toggler::toggler;
 
@@ -12,6 +12,7 @@ use iced_core::widget::{Operation, OperationOutputWrapper, Tree};
 use iced_core::{Clipboard, Element, Layout, Length, Point, Rectangle, Shell, Size, Widget};
 use std::cell::RefCell;
 
+use crate::theme::elevation::{self, ElevationLevel};
 pub use iced_style::container::{Appearance, StyleSheet};
 
 pub fn popover<'a, Message, Renderer>(
@@ -44,7 +45,7 @@ impl<'a, Message, Renderer> Popover<'a, Message, Renderer> {
 impl<'a, Message, Renderer> Widget<Message, Renderer> for Popover<'a, Message, Renderer>
 where
     Renderer: iced_core::Renderer,
-    Renderer::Theme: StyleSheet,
+    Renderer::Theme: StyleSheet + elevation::StyleSheet,
 {
     fn children(&self) -> Vec<Tree> {
         vec![Tree::new(&self.content), Tree::new(&*self.popup.borrow())]
@@ -164,7 +165,7 @@ impl<'a, Message, Renderer> From<Popover<'a, Message, Renderer>> for Element<'a,
 where
     Message: 'static,
     Renderer: iced_core::Renderer + 'static,
-    Renderer::Theme: StyleSheet,
+    Renderer::Theme: StyleSheet + elevation::StyleSheet,
 {
     fn from(popover: Popover<'a, Message, Renderer>) -> Self {
         Self::new(popover)
@@ -180,6 +181,7 @@ impl<'a, 'b, Message, Renderer> overlay::Overlay<Message, Renderer>
     for Overlay<'a, 'b, Message, Renderer>
 where
     Renderer: iced_core::Renderer,
+    Renderer::Theme: elevation::StyleSheet,
 {
     fn layout(&self, renderer: &Renderer, bounds: Size, mut position: Point) -> layout::Node {
         // Position is set to the center bottom of the lower widget
@@ -252,6 +254,9 @@ where
         cursor_position: mouse::Cursor,
     ) {
         let bounds = layout.bounds();
+        let elevation = theme.elevation(ElevationLevel::Overlay);
+        elevation::draw_shadow(renderer, bounds, &elevation);
+
         self.content.borrow().as_widget().draw(
             self.tree,
             renderer,
@@ -261,5 +266,7 @@ where
             cursor_position,
             &bounds,
         );
+
+        elevation::draw_outline(renderer, bounds, &elevation);
     }
 }
@@ -0,0 +1,317 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Layouts which adapt to the width available to them.
+
+use crate::{Element, Renderer, Theme};
+use iced_core::event::{self, Event};
+use iced_core::widget::{Operation, OperationOutputWrapper, Tree};
+use iced_core::{layout, mouse, overlay, renderer};
+use iced_core::{Clipboard, Layout, Length, Rectangle, Shell, Widget};
+use std::cell::Cell;
+
+/// A range of widths which a layout is designed for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Breakpoint {
+    /// Too narrow to show the nav bar beside the content, such as on phones.
+    #[default]
+    Narrow,
+    /// Wide enough for the nav bar and the content.
+    Medium,
+    /// Wide enough for additional columns, such as a context drawer beside the content.
+    Wide,
+}
+
+/// The minimum widths of each [`Breakpoint`] above [`Breakpoint::Narrow`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Breakpoints {
+    /// Minimum width of [`Breakpoint::Medium`].
+    pub medium: f32,
+    /// Minimum width of [`Breakpoint::Wide`].
+    pub wide: f32,
+}
+
+impl Default for Breakpoints {
+    fn default() -> Self {
+        Self {
+            medium: 600.0,
+            wide: 1200.0,
+        }
+    }
+}
+
+impl Breakpoints {
+    /// The breakpoint which a width falls in.
+    #[must_use]
+    pub fn breakpoint(&self, width: f32) -> Breakpoint {
+        if width >= self.wide {
+            Breakpoint::Wide
+        } else if width >= self.medium {
+            Breakpoint::Medium
+        } else {
+            Breakpoint::Narrow
+        }
+    }
+}
+
+/// Shows `narrow` until other views are added for wider breakpoints.
+pub fn responsive<'a, Message>(narrow: impl Into<Element<'a, Message>>) -> Responsive<'a, Message> {
+    Responsive::new(narrow)
+}
+
+/// Shows the view of the [`Breakpoint`] which the width available to it falls in.
+///
+/// A breakpoint without a view shows the view of the next narrower breakpoint.
+pub struct Responsive<'a, Message> {
+    breakpoints: Breakpoints,
+    views: Vec<Element<'a, Message>>,
+    medium: Option<usize>,
+    wide: Option<usize>,
+    /// The view chosen by the last layout.
+    active: Cell<usize>,
+}
+
+impl<'a, Message> Responsive<'a, Message> {
+    /// Shows `narrow` at every breakpoint which has no other view.
+    pub fn new(narrow: impl Into<Element<'a, Message>>) -> Self {
+        Self {
+            breakpoints: Breakpoints::default(),
+            views: vec![narrow.into()],
+            medium: None,
+            wide: None,
+            active: Cell::new(0),
+        }
+    }
+
+    /// The widths at which views change, such as those of
+    /// [`Core::breakpoints`](crate::app::Core::breakpoints).
+    #[must_use]
+    pub fn breakpoints(mut self, breakpoints: Breakpoints) -> Self {
+        self.breakpoints = breakpoints;
+        self
+    }
+
+    /// The view of [`Breakpoint::Medium`].
+    #[must_use]
+    pub fn medium(mut self, view: impl Into<Element<'a, Message>>) -> Self {
+        self.views.push(view.into());
+        self.medium = Some(self.views.len() - 1);
+        self
+    }
+
+    /// The view of [`Breakpoint::Wide`].
+    #[must_use]
+    pub fn wide(mut self, view: impl Into<Element<'a, Message>>) -> Self {
+        self.views.push(view.into());
+        self.wide = Some(self.views.len() - 1);
+        self
+    }
+
+    /// The index of the view shown at a breakpoint.
+    fn view_index(&self, breakpoint: Breakpoint) -> usize {
+        match breakpoint {
+            Breakpoint::Narrow => None,
+            Breakpoint::Medium => self.medium,
+            Breakpoint::Wide => self.wide.or(self.medium),
+        }
+        .unwrap_or(0)
+    }
+}
+
+impl<'a, Message> Widget<Message, Renderer> for Responsive<'a, Message> {
+    fn children(&self) -> Vec<Tree> {
+        self.views.iter().map(Tree::new).collect()
+    }
+
+    fn diff(&mut self, tree: &mut Tree) {
+        tree.diff_children(&mut self.views);
+    }
+
+    fn width(&self) -> Length {
+        Length::Fill
+    }
+
+    fn height(&self) -> Length {
+        self.views[0].as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let breakpoint = self.breakpoints.breakpoint(limits.max().width);
+        let active = self.view_index(breakpoint);
+        self.active.set(active);
+
+        let view = self.views[active].as_widget().layout(renderer, limits);
+        layout::Node::with_children(view.size(), vec![view])
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<OperationOutputWrapper<Message>>,
+    ) {
+        let active = self.active.get();
+        if let Some(view) = layout.children().next() {
+            self.views[active].as_widget().operate(
+                &mut tree.children[active],
+                view,
+                renderer,
+                operation,
+            );
+        }
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let active = self.active.get();
+        let Some(view) = layout.children().next() else {
+            return event::Status::Ignored;
+        };
+
+        self.views[active].as_widget_mut().on_event(
+            &mut tree.children[active],
+            event,
+            view,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        let active = self.active.get();
+        layout
+            .children()
+            .next()
+            .map_or(mouse::Interaction::Idle, |view| {
+                self.views[active].as_widget().mouse_interaction(
+                    &tree.children[active],
+                    view,
+                    cursor_position,
+                    viewport,
+                    renderer,
+                )
+            })
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        theme: &Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        let active = self.active.get();
+        if let Some(view) = layout.children().next() {
+            self.views[active].as_widget().draw(
+                &tree.children[active],
+                renderer,
+                theme,
+                style,
+                view,
+                cursor_position,
+                viewport,
+            );
+        }
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        let active = self.active.get();
+        let view = layout.children().next()?;
+
+        self.views[active]
+            .as_widget_mut()
+            .overlay(&mut tree.children[active], view, renderer)
+    }
+}
+
+impl<'a, Message: 'a> From<Responsive<'a, Message>> for Element<'a, Message> {
+    fn from(responsive: Responsive<'a, Message>) -> Self {
+        Self::new(responsive)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{responsive, Breakpoint, Breakpoints, Responsive};
+    use crate::widget::Space;
+    use iced_core::Length;
+
+    fn view() -> Space {
+        Space::new(Length::Shrink, Length::Shrink)
+    }
+
+    #[test]
+    fn test_breakpoint_boundaries() {
+        let breakpoints = Breakpoints::default();
+        assert_eq!(breakpoints.breakpoint(0.0), Breakpoint::Narrow);
+        assert_eq!(breakpoints.breakpoint(599.9), Breakpoint::Narrow);
+        // the minimum width of a breakpoint is part of it
+        assert_eq!(breakpoints.breakpoint(600.0), Breakpoint::Medium);
+        assert_eq!(breakpoints.breakpoint(1199.9), Breakpoint::Medium);
+        assert_eq!(breakpoints.breakpoint(1200.0), Breakpoint::Wide);
+        assert_eq!(breakpoints.breakpoint(f32::INFINITY), Breakpoint::Wide);
+    }
+
+    #[test]
+    fn test_custom_breakpoints() {
+        let breakpoints = Breakpoints {
+            medium: 400.0,
+            wide: 400.0,
+        };
+        assert_eq!(breakpoints.breakpoint(399.0), Breakpoint::Narrow);
+        // the widest breakpoint wins when they share a minimum width
+        assert_eq!(breakpoints.breakpoint(400.0), Breakpoint::Wide);
+    }
+
+    #[test]
+    fn test_view_index() {
+        let narrow: Responsive<'_, ()> = responsive(view());
+        assert_eq!(narrow.view_index(Breakpoint::Narrow), 0);
+        assert_eq!(narrow.view_index(Breakpoint::Medium), 0);
+        assert_eq!(narrow.view_index(Breakpoint::Wide), 0);
+
+        let medium: Responsive<'_, ()> = responsive(view()).medium(view());
+        assert_eq!(medium.view_index(Breakpoint::Narrow), 0);
+        assert_eq!(medium.view_index(Breakpoint::Medium), 1);
+        assert_eq!(medium.view_index(Breakpoint::Wide), 1);
+
+        // a wide view without a medium view leaves medium widths to the narrow view
+        let wide: Responsive<'_, ()> = responsive(view()).wide(view());
+        assert_eq!(wide.view_index(Breakpoint::Narrow), 0);
+        assert_eq!(wide.view_index(Breakpoint::Medium), 0);
+        assert_eq!(wide.view_index(Breakpoint::Wide), 1);
+
+        let all: Responsive<'_, ()> = responsive(view()).wide(view()).medium(view());
+        assert_eq!(all.view_index(Breakpoint::Narrow), 0);
+        assert_eq!(all.view_index(Breakpoint::Medium), 2);
+        assert_eq!(all.view_index(Breakpoint::Wide), 1);
+    }
+}
@@ -4,6 +4,7 @@
 use super::model::{Entity, Model, Selectable};
 use super::style::StyleSheet;
 use super::IconColor;
+use crate::theme::THEME;
 use crate::widget::{icon, IconSource};
 use derive_setters::Setters;
 use iced::{
@@ -136,14 +137,20 @@ where
 {
     #[must_use]
     pub fn new(model: &'a Model<SelectionMode>) -> Self {
+        let (space_xxxs, button_height) = THEME.with(|t| {
+            let theme = t.borrow();
+            let cosmic = theme.cosmic();
+            (cosmic.space_xxxs(), cosmic.layout.segmented_button_height)
+        });
+
         Self {
             model,
             id: None,
             close_icon: IconSource::from("window-close-symbolic"),
             show_close_icon_on_hover: false,
-            button_padding: [4, 4, 4, 4],
-            button_height: 32,
-            button_spacing: 4,
+            button_padding: [space_xxxs; 4],
+            button_height,
+            button_spacing: space_xxxs,
             font_active: None,
             font_hovered: None,
             font_inactive: None,
@@ -5,6 +5,8 @@
 //!
 //! See the [`segmented_button`] module for more details.
 
+use crate::theme::THEME;
+
 use super::segmented_button::{
     self, HorizontalSegmentedButton, Model, Selectable, VerticalSegmentedButton,
 };
@@ -21,11 +23,12 @@ pub fn horizontal<SelectionMode: Default, Message>(
 where
     Model<SelectionMode>: Selectable,
 {
+    let (space_s, button_height) = segmented_selection_layout();
     segmented_button::horizontal(model)
-        .button_padding([16, 0, 16, 0])
-        .button_height(32)
+        .button_padding([space_s, 0, space_s, 0])
+        .button_height(button_height)
         .style(crate::theme::SegmentedButton::Selection)
-        .font_active(Some(crate::font::FONT_SEMIBOLD))
+        .font_active(Some(crate::font::heading()))
 }
 
 /// A selection of multiple choices appearing as a conjoined button.
@@ -41,9 +44,19 @@ where
     Model<SelectionMode>: Selectable,
     SelectionMode: Default,
 {
+    let (space_s, button_height) = segmented_selection_layout();
     segmented_button::vertical(model)
-        .button_padding([16, 0, 16, 0])
-        .button_height(32)
+        .button_padding([space_s, 0, space_s, 0])
+        .button_height(button_height)
         .style(crate::theme::SegmentedButton::Selection)
-        .font_active(Some(crate::font::FONT_SEMIBOLD))
+        .font_active(Some(crate::font::heading()))
+}
+
+/// Padding and height of selection buttons for the active layout density.
+fn segmented_selection_layout() -> (u16, u16) {
+    THEME.with(|t| {
+        let theme = t.borrow();
+        let cosmic = theme.cosmic();
+        (cosmic.space_s(), cosmic.layout.segmented_button_height)
+    })
 }
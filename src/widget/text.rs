@@ -1,5 +1,7 @@
 use std::borrow::Cow;
 
+use crate::{font, theme::THEME};
+use cosmic_theme::{TextStyle, Typography};
 pub use iced::widget::Text;
 
 /// Creates a new [`Text`] widget with the provided content.
@@ -12,3 +14,48 @@ where
 {
     Text::new(text)
 }
+
+/// Creates a new [`Text`] widget styled as a title by the COSMIC theme.
+pub fn title<'a>(text: impl Into<Cow<'a, str>>) -> Text<'a, crate::Renderer> {
+    styled(text, |typography| &typography.title)
+}
+
+/// Creates a new [`Text`] widget styled as a heading by the COSMIC theme.
+pub fn heading<'a>(text: impl Into<Cow<'a, str>>) -> Text<'a, crate::Renderer> {
+    styled(text, |typography| &typography.heading)
+}
+
+/// Creates a new [`Text`] widget styled as body text by the COSMIC theme.
+pub fn body<'a>(text: impl Into<Cow<'a, str>>) -> Text<'a, crate::Renderer> {
+    styled(text, |typography| &typography.body)
+}
+
+/// Creates a new [`Text`] widget styled as a caption by the COSMIC theme.
+pub fn caption<'a>(text: impl Into<Cow<'a, str>>) -> Text<'a, crate::Renderer> {
+    styled(text, |typography| &typography.caption)
+}
+
+/// Creates a new [`Text`] widget styled as monospaced text by the COSMIC theme.
+pub fn monospace<'a>(text: impl Into<Cow<'a, str>>) -> Text<'a, crate::Renderer> {
+    THEME.with(|t| {
+        let theme = t.borrow();
+        let typography = &theme.cosmic().typography;
+        Text::new(text)
+            .size(typography.monospace.size)
+            .font(font::monospace(typography))
+    })
+}
+
+fn styled<'a>(
+    text: impl Into<Cow<'a, str>>,
+    style: impl Fn(&Typography) -> &TextStyle,
+) -> Text<'a, crate::Renderer> {
+    THEME.with(|t| {
+        let theme = t.borrow();
+        let typography = &theme.cosmic().typography;
+        let style = style(typography);
+        Text::new(text)
+            .size(style.size)
+            .font(font::from_text_style(typography, style))
+    })
+}
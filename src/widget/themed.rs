@@ -0,0 +1,273 @@
+// Copyright 2023 System76 <info@system76.com>
+// SPDX-License-Identifier: MPL-2.0
+
+//! Renders a subtree of widgets with a different theme than the rest of the window.
+
+use crate::theme::{self, Theme};
+use crate::{Element, Renderer};
+use cosmic_theme::Layer;
+use iced_core::event::{self, Event};
+use iced_core::layout;
+use iced_core::mouse;
+use iced_core::overlay;
+use iced_core::renderer;
+use iced_core::widget::{Operation, OperationOutputWrapper, Tree};
+use iced_core::{Clipboard, Layout, Length, Point, Rectangle, Shell, Size, Widget};
+
+/// Constructs the content with `theme`, and draws it with `theme`.
+///
+/// Widgets which read the theme while they are constructed, such as for their spacing and
+/// fonts, must be created inside of `content` to use the overriding theme.
+pub fn themed<'a, Message: 'static>(
+    theme: Theme,
+    content: impl FnOnce() -> Element<'a, Message>,
+) -> Themed<'a, Message> {
+    let content = theme::with_theme(&theme, content);
+    Themed::new(theme, content)
+}
+
+/// A subtree of widgets which uses a different theme than its parent.
+#[allow(missing_debug_implementations)]
+pub struct Themed<'a, Message> {
+    theme: Theme,
+    content: Element<'a, Message>,
+}
+
+impl<'a, Message: 'static> Themed<'a, Message> {
+    /// Creates a [`Themed`] subtree from content which has already been constructed.
+    pub fn new(theme: Theme, content: impl Into<Element<'a, Message>>) -> Self {
+        Self {
+            theme,
+            content: content.into(),
+        }
+    }
+
+    /// Sets the [`Layer`] of the subtree, and draws the container background of that layer.
+    #[must_use]
+    pub fn layer(mut self, layer: Layer) -> Self {
+        self.theme.layer = layer;
+        let width = self.content.as_widget().width();
+        let height = self.content.as_widget().height();
+        self.content = crate::widget::container(self.content)
+            .layer(layer)
+            .width(width)
+            .height(height)
+            .into();
+        self
+    }
+}
+
+impl<'a, Message> Widget<Message, Renderer> for Themed<'a, Message> {
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&mut self, tree: &mut Tree) {
+        tree.diff_children(&mut [&mut self.content]);
+    }
+
+    fn width(&self) -> Length {
+        self.content.as_widget().width()
+    }
+
+    fn height(&self) -> Length {
+        self.content.as_widget().height()
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        theme::with_theme(&self.theme, || {
+            self.content.as_widget().layout(renderer, limits)
+        })
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<OperationOutputWrapper<Message>>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        self.content.as_widget_mut().on_event(
+            &mut tree.children[0],
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+            viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor_position,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _renderer_style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        // text inherits its color from the overriding theme instead of the parent
+        let style = renderer::Style {
+            text_color: self.theme.current_container().on.into(),
+        };
+
+        theme::with_theme(&self.theme, || {
+            self.content.as_widget().draw(
+                &tree.children[0],
+                renderer,
+                &self.theme,
+                &style,
+                layout,
+                cursor_position,
+                viewport,
+            );
+        });
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        let theme = &self.theme;
+        let content =
+            self.content
+                .as_widget_mut()
+                .overlay(&mut tree.children[0], layout, renderer)?;
+
+        Some(Overlay::element(theme, content))
+    }
+}
+
+impl<'a, Message: 'static> From<Themed<'a, Message>> for Element<'a, Message> {
+    fn from(themed: Themed<'a, Message>) -> Self {
+        Self::new(themed)
+    }
+}
+
+/// Draws the overlays of a [`Themed`] subtree, such as popovers, with its theme.
+struct Overlay<'a, Message> {
+    theme: &'a Theme,
+    content: overlay::Element<'a, Message, Renderer>,
+}
+
+impl<'a, Message> Overlay<'a, Message> {
+    fn element(
+        theme: &'a Theme,
+        content: overlay::Element<'a, Message, Renderer>,
+    ) -> overlay::Element<'a, Message, Renderer> {
+        // the position of the content is kept by the content itself
+        overlay::Element::new(Point::ORIGIN, Box::new(Self { theme, content }))
+    }
+}
+
+impl<'a, Message> overlay::Overlay<Message, Renderer> for Overlay<'a, Message> {
+    fn layout(&self, renderer: &Renderer, bounds: Size, position: Point) -> layout::Node {
+        theme::with_theme(self.theme, || {
+            self.content
+                .layout(renderer, bounds, position - Point::ORIGIN)
+        })
+    }
+
+    fn operate(
+        &mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+        operation: &mut dyn Operation<OperationOutputWrapper<Message>>,
+    ) {
+        self.content.operate(layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        self.content
+            .on_event(event, layout, cursor_position, renderer, clipboard, shell)
+    }
+
+    fn mouse_interaction(
+        &self,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .mouse_interaction(layout, cursor_position, viewport, renderer)
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        _theme: &Theme,
+        _style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: mouse::Cursor,
+    ) {
+        let style = renderer::Style {
+            text_color: self.theme.current_container().on.into(),
+        };
+
+        theme::with_theme(self.theme, || {
+            self.content
+                .draw(renderer, self.theme, &style, layout, cursor_position);
+        });
+    }
+
+    fn is_over(&self, layout: Layout<'_>, renderer: &Renderer, cursor_position: Point) -> bool {
+        self.content.is_over(layout, renderer, cursor_position)
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        let theme = self.theme;
+        let content = self.content.overlay(layout, renderer)?;
+        Some(Overlay::element(theme, content))
+    }
+}
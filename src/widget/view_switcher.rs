@@ -5,6 +5,8 @@
 //!
 //! See the [`segmented_button`] module for more details.
 
+use crate::theme::THEME;
+
 use super::segmented_button::{
     self, HorizontalSegmentedButton, Model, SegmentedButton, Selectable, VerticalSegmentedButton,
 };
@@ -21,11 +23,12 @@ pub fn horizontal<SelectionMode: Default, Message>(
 where
     Model<SelectionMode>: Selectable,
 {
+    let (space_s, view_switcher_height) = view_switcher_layout();
     segmented_button::horizontal(model)
-        .button_padding([16, 0, 16, 0])
-        .button_height(48)
+        .button_padding([space_s, 0, space_s, 0])
+        .button_height(view_switcher_height)
         .style(crate::theme::SegmentedButton::ViewSwitcher)
-        .font_active(Some(crate::font::FONT_SEMIBOLD))
+        .font_active(Some(crate::font::heading()))
 }
 
 /// A collection of tabs for developing a tabbed interface.
@@ -41,9 +44,19 @@ where
     Model<SelectionMode>: Selectable,
     SelectionMode: Default,
 {
+    let (space_s, view_switcher_height) = view_switcher_layout();
     SegmentedButton::new(model)
-        .button_padding([16, 0, 16, 0])
-        .button_height(48)
+        .button_padding([space_s, 0, space_s, 0])
+        .button_height(view_switcher_height)
         .style(crate::theme::SegmentedButton::ViewSwitcher)
-        .font_active(Some(crate::font::FONT_SEMIBOLD))
+        .font_active(Some(crate::font::heading()))
+}
+
+/// Padding and height of view switcher buttons for the active layout density.
+fn view_switcher_layout() -> (u16, u16) {
+    THEME.with(|t| {
+        let theme = t.borrow();
+        let cosmic = theme.cosmic();
+        (cosmic.space_s(), cosmic.layout.view_switcher_height)
+    })
 }